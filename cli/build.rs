@@ -0,0 +1,37 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Вшивает каталоги сообщений в бинарник: в контейнере задания их файлов нет.
+fn main() {
+    let locales_dir = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap())
+        .parent()
+        .unwrap()
+        .join("locales");
+    let out = PathBuf::from(env::var("OUT_DIR").unwrap()).join("locales.rs");
+
+    println!("cargo:rerun-if-changed={}", locales_dir.display());
+
+    let mut catalogs: Vec<PathBuf> = match fs::read_dir(&locales_dir) {
+        Ok(read_dir) => read_dir
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "toml"))
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    catalogs.sort();
+
+    let mut code = String::from("pub static LOCALES: &[(&str, &str)] = &[\n");
+    for catalog in catalogs {
+        println!("cargo:rerun-if-changed={}", catalog.display());
+        code.push_str(&format!(
+            "    ({:?}, include_str!({:?})),\n",
+            catalog.file_stem().unwrap().to_string_lossy(),
+            catalog.display().to_string()
+        ));
+    }
+    code.push_str("];\n");
+
+    fs::write(out, code).unwrap();
+}
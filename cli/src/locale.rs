@@ -0,0 +1,112 @@
+use serde::Deserialize;
+use std::env;
+use std::fmt::Display;
+use std::sync::OnceLock;
+
+/// git-trainer sets it when it creates the task container
+const LOCALE_ENV: &str = "GIT_TRAINER_LOCALE";
+/// Messages missing from the selected catalog are taken from this one
+const DEFAULT_LOCALE: &str = "ru";
+
+/// Every user-facing string of the CLI, the `[cli]` section of `locales/<locale>.toml`.
+/// `{name}` placeholders are filled with `fill`
+#[derive(Deserialize)]
+pub struct Messages {
+    pub about: String,
+    pub restart_about: String,
+    pub task_about: String,
+    pub submit_about: String,
+    pub hint_about: String,
+    pub yes_help: String,
+    pub help_help: String,
+    pub usage_heading: String,
+    pub commands_heading: String,
+    pub options_heading: String,
+
+    pub expected: String,
+    pub actual: String,
+    pub hint: String,
+    pub score: String,
+    pub all_tests_passed: String,
+    pub not_all_tests_passed: String,
+
+    pub connection_failed: String,
+    pub not_acknowledged: String,
+    pub confirm_suffix: String,
+    pub restart_question: String,
+    pub restarting: String,
+    pub submitted: String,
+    pub no_result: String,
+    pub hint_question: String,
+    pub no_hints: String,
+    pub hint_line: String,
+    pub all_hints_revealed: String,
+    pub no_hint: String,
+}
+
+mod embedded {
+    include!(concat!(env!("OUT_DIR"), "/locales.rs"));
+}
+
+static MESSAGES: OnceLock<Messages> = OnceLock::new();
+
+/// The `[cli]` section of the built-in catalog of the locale
+fn cli_section(locale: &str) -> Option<toml::Table> {
+    let (_, content) = embedded::LOCALES.iter().find(|(name, _)| *name == locale)?;
+    let mut catalog: toml::Table = toml::from_str(content).ok()?;
+    match catalog.remove("cli")? {
+        toml::Value::Table(section) => Some(section),
+        _ => None,
+    }
+}
+
+/// Messages of the locale git-trainer runs with. As in git-trainer itself, messages
+/// missing from its catalog, or of a locale without a catalog, are taken from the default one
+fn load_messages(locale: Option<&str>) -> Result<Messages, toml::de::Error> {
+    let mut section = cli_section(DEFAULT_LOCALE).unwrap_or_default();
+    if let Some(overrides) = locale.and_then(cli_section) {
+        section.extend(overrides);
+    }
+    section.try_into()
+}
+
+pub fn messages() -> &'static Messages {
+    MESSAGES.get_or_init(|| {
+        let locale = env::var(LOCALE_ENV).ok();
+        // Каталоги вшиваются при сборке, и тест ниже проверяет, что в них есть все сообщения
+        load_messages(locale.as_deref()).expect("built-in catalogs have every CLI message")
+    })
+}
+
+/// Replaces `{name}` placeholders of the message with the values
+pub fn fill(message: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut message = message.to_string();
+    for (name, value) in args {
+        message = message.replace(&format!("{{{}}}", name), &value.to_string());
+    }
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_catalog_has_all_messages() {
+        for (locale, content) in embedded::LOCALES {
+            let catalog: toml::Table = toml::from_str(content).unwrap();
+            let section = catalog["cli"].as_table().unwrap().clone();
+            let messages: Result<Messages, _> = section.try_into();
+            assert!(messages.is_ok(), "{locale}: {:?}", messages.err());
+        }
+    }
+
+    #[test]
+    fn locale_by_environment() {
+        assert_eq!(load_messages(Some("en")).unwrap().hint, "Hint");
+        assert_eq!(load_messages(Some("ru")).unwrap().hint, "Подсказка");
+        // Языка без каталога нет, сообщения берутся из каталога по умолчанию
+        assert_eq!(load_messages(Some("xx")).unwrap().hint, "Подсказка");
+        assert_eq!(load_messages(None).unwrap().hint, "Подсказка");
+    }
+}
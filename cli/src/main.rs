@@ -1,12 +1,22 @@
-use clap::{Parser, Subcommand};
+mod locale;
+
+use clap::{Arg, ArgAction, Command, CommandFactory, FromArgMatches, Parser, Subcommand};
+use locale::{fill, messages};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
 use std::fs;
 use std::io;
-use std::io::Write;
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::UnixStream;
+
+/// Сокет, через который CLI связывается с git-trainer
+const SOCKET_PATH: &str = "/run/git-trainer/git-trainer.sock";
 
 #[derive(Parser)]
 #[command(name = "git-trainer CLI")]
 #[command(about = "git-trainer CLI", long_about = None)]
 #[command(disable_help_subcommand = true)]
+#[command(disable_help_flag = true)]
 struct Cli {
     #[command(subcommand)]
     command: Commands,
@@ -25,10 +35,145 @@ enum Commands {
 
     /// Отправить текущее задание на проверку
     Submit,
+
+    /// Открыть следующую подсказку к текущему заданию
+    Hint {
+        #[arg(short = 'y', long = "yes")]
+        yes: bool,
+    },
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Request {
+    Submit,
+    Restart,
+    Hint,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Response {
+    Ack,
+    Result {
+        tests: Vec<TestReport>,
+    },
+    Hints {
+        hints: Vec<String>,
+        total: usize,
+        revealed: bool,
+    },
+    Error {
+        message: String,
+    },
+}
+
+#[derive(Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+enum TestResult {
+    Passed,
+    Failed,
+    NotExecuted,
+    TimedOut,
+}
+
+#[derive(Deserialize)]
+struct TestReport {
+    description: String,
+    result: TestResult,
+    hint: Option<String>,
+    expected: Option<String>,
+    actual: Option<String>,
+    score: Option<f64>,
+}
+
+const GREEN: &str = "\x1b[32m";
+const RED: &str = "\x1b[31m";
+const YELLOW: &str = "\x1b[33m";
+const GRAY: &str = "\x1b[90m";
+const RESET: &str = "\x1b[0m";
+
+fn print_results(tests: &[TestReport]) {
+    let m = messages();
+    println!();
+    for test in tests {
+        let (mark, color) = match test.result {
+            TestResult::Passed => ("✔", GREEN),
+            TestResult::Failed => ("✘", RED),
+            TestResult::NotExecuted => ("–", GRAY),
+            TestResult::TimedOut => ("⌛", YELLOW),
+        };
+        println!("{color}{mark} {}{RESET}", test.description.trim());
+
+        let details = [
+            (m.expected.as_str(), test.expected.clone()),
+            (m.actual.as_str(), test.actual.clone()),
+            (m.hint.as_str(), test.hint.clone()),
+            (m.score.as_str(), test.score.map(|score| score.to_string())),
+        ];
+        for (label, value) in details {
+            if let Some(value) = value {
+                println!("    {label}: {value}");
+            }
+        }
+    }
+    println!();
+
+    let passed = tests
+        .iter()
+        .filter(|test| test.result == TestResult::Passed)
+        .count();
+    let counts: [(&str, &dyn Display); 2] = [("passed", &passed), ("total", &tests.len())];
+    if passed == tests.len() {
+        println!("{GREEN}{}{RESET}", fill(&m.all_tests_passed, &counts));
+    } else {
+        println!("{}", fill(&m.not_all_tests_passed, &counts));
+    }
+}
+
+/// Соединение с git-trainer: запрос отправляется одной строкой JSON, ответы приходят так же
+struct Connection {
+    reader: BufReader<UnixStream>,
+}
+
+impl Connection {
+    fn send(request: &Request) -> io::Result<Connection> {
+        let mut stream = UnixStream::connect(SOCKET_PATH).map_err(|e| {
+            io::Error::new(
+                e.kind(),
+                fill(&messages().connection_failed, &[("error", &e)]),
+            )
+        })?;
+
+        let mut line = serde_json::to_string(request)?;
+        line.push('\n');
+        stream.write_all(line.as_bytes())?;
+
+        Ok(Connection {
+            reader: BufReader::new(stream),
+        })
+    }
+
+    /// Возвращает None, если git-trainer закрыл соединение
+    fn receive(&mut self) -> io::Result<Option<Response>> {
+        let mut line = String::new();
+        if self.reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_str(&line)?))
+    }
+
+    fn wait_ack(&mut self) -> io::Result<()> {
+        match self.receive()? {
+            Some(Response::Ack) => Ok(()),
+            Some(Response::Error { message }) => Err(io::Error::other(message)),
+            _ => Err(io::Error::other(messages().not_acknowledged.as_str())),
+        }
+    }
 }
 
 fn confirm(question: &str) -> io::Result<bool> {
-    print!("{question} [Y/n]: ");
+    print!("{question} {}: ", messages().confirm_suffix);
     io::stdout().flush()?;
 
     let mut line = String::new();
@@ -40,24 +185,61 @@ fn confirm(question: &str) -> io::Result<bool> {
     }
 }
 
-fn main() -> io::Result<()> {
-    let cli = Cli::parse();
-
-    match cli.command {
+fn run(command: Commands) -> io::Result<()> {
+    let m = messages();
+    match command {
         Commands::Restart { yes } => {
-            if !yes
-                && !confirm("Перезагрузить текущее задание? Вы потеряете весь текущий прогресс.")?
-            {
+            if !yes && !confirm(&m.restart_question)? {
                 return Ok(());
             }
-            fs::write("/etc/git-trainer/status", "1")?;
+            Connection::send(&Request::Restart)?.wait_ack()?;
+            println!("{}", m.restarting);
         }
         Commands::Submit => {
-            fs::write("/etc/git-trainer/status", "2")?;
-            println!("Попытка отправлена! Вы можете выйти из задания командой \"exit\" и посмотреть оценку в менеджере попыток");
+            let mut connection = Connection::send(&Request::Submit)?;
+            connection.wait_ack()?;
+            println!("{}", m.submitted);
+
+            match connection.receive()? {
+                Some(Response::Result { tests }) => print_results(&tests),
+                Some(Response::Error { message }) => return Err(io::Error::other(message)),
+                _ => return Err(io::Error::other(m.no_result.as_str())),
+            }
+        }
+        Commands::Hint { yes } => {
+            if !yes && !confirm(&m.hint_question)? {
+                return Ok(());
+            }
+
+            let mut connection = Connection::send(&Request::Hint)?;
+            connection.wait_ack()?;
+            match connection.receive()? {
+                Some(Response::Hints { total: 0, .. }) => {
+                    println!("{}", m.no_hints);
+                }
+                Some(Response::Hints {
+                    hints,
+                    total,
+                    revealed,
+                }) => {
+                    for (i, hint) in hints.iter().enumerate() {
+                        println!(
+                            "{}",
+                            fill(
+                                &m.hint_line,
+                                &[("number", &(i + 1)), ("total", &total), ("hint", hint)],
+                            )
+                        );
+                    }
+                    if !revealed {
+                        println!("{}", m.all_hints_revealed);
+                    }
+                }
+                Some(Response::Error { message }) => return Err(io::Error::other(message)),
+                _ => return Err(io::Error::other(m.no_hint.as_str())),
+            }
         }
         Commands::Task => {
-            fs::write("/etc/git-trainer/status", "0")?;
             let bytes = fs::read("/etc/git-trainer/description")?;
             let description = String::from_utf8(bytes).unwrap();
             println!("{description}");
@@ -66,3 +248,49 @@ fn main() -> io::Result<()> {
 
     Ok(())
 }
+
+/// Help of the command and of its subcommands in the language of the task
+fn localized_command() -> Command {
+    let m = messages();
+    let template = format!(
+        "{{about-with-newline}}\n{} {{usage}}\n\n{{all-args}}{{after-help}}",
+        m.usage_heading
+    );
+    let localize_args = |command: Command| {
+        command
+            .help_template(&template)
+            .mut_args(|arg| arg.help_heading(m.options_heading.as_str()))
+    };
+
+    let command = Cli::command()
+        .about(m.about.as_str())
+        .arg(
+            Arg::new("help")
+                .short('h')
+                .long("help")
+                .action(ArgAction::Help)
+                .help(m.help_help.as_str())
+                .global(true),
+        )
+        .subcommand_help_heading(m.commands_heading.as_str())
+        .mut_subcommand("restart", |c| {
+            c.about(m.restart_about.as_str())
+                .mut_arg("yes", |a| a.help(m.yes_help.as_str()))
+        })
+        .mut_subcommand("task", |c| c.about(m.task_about.as_str()))
+        .mut_subcommand("submit", |c| c.about(m.submit_about.as_str()))
+        .mut_subcommand("hint", |c| {
+            c.about(m.hint_about.as_str())
+                .mut_arg("yes", |a| a.help(m.yes_help.as_str()))
+        });
+    localize_args(command).mut_subcommands(localize_args)
+}
+
+fn main() {
+    let matches = localized_command().get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    if let Err(err) = run(cli.command) {
+        eprintln!("{err}");
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,4 @@
+ALTER TABLE tasks DROP COLUMN tags;
+ALTER TABLE tasks DROP COLUMN difficulty;
+ALTER TABLE tasks DROP COLUMN author;
+ALTER TABLE tasks DROP COLUMN image;
@@ -0,0 +1,4 @@
+ALTER TABLE tasks ADD COLUMN image TEXT;
+ALTER TABLE tasks ADD COLUMN author TEXT;
+ALTER TABLE tasks ADD COLUMN difficulty TEXT;
+ALTER TABLE tasks ADD COLUMN tags TEXT NOT NULL DEFAULT '';
@@ -0,0 +1,4 @@
+ALTER TABLE attempt_tests DROP COLUMN score;
+ALTER TABLE attempt_tests DROP COLUMN actual;
+ALTER TABLE attempt_tests DROP COLUMN expected;
+ALTER TABLE attempt_tests DROP COLUMN hint;
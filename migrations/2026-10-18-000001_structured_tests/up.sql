@@ -0,0 +1,4 @@
+ALTER TABLE attempt_tests ADD COLUMN hint TEXT;
+ALTER TABLE attempt_tests ADD COLUMN expected TEXT;
+ALTER TABLE attempt_tests ADD COLUMN actual TEXT;
+ALTER TABLE attempt_tests ADD COLUMN score REAL;
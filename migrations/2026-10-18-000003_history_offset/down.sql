@@ -0,0 +1 @@
+ALTER TABLE user_task_statuses DROP COLUMN history_offset;
@@ -0,0 +1 @@
+ALTER TABLE user_task_statuses ADD COLUMN history_offset INTEGER NOT NULL DEFAULT 0;
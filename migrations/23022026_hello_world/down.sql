@@ -1 +0,0 @@
-DELETE FROM tasks WHERE work_name = 'hello-world';
@@ -1 +0,0 @@
-DELETE FROM tasks WHERE work_name = 'just-push-it';
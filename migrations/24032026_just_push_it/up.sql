@@ -1,14 +0,0 @@
-INSERT INTO tasks (
-    id,
-    name,
-    work_name,
-    description,
-    extended_description
-)
-VALUES (
-    5,
-    'Всего один push',
-    'just-push-it',
-    'Вам нужно просто запушить свой коммит :)',
-    'Вам нужно реализовать функцию max_of_two в файле main.cpp, сделать один коммит с вашим изменением и запушить его в origin. Всё! Действуйте :)'
-);
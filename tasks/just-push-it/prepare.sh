@@ -0,0 +1,27 @@
+#!/usr/bin/env bash
+# Запускается git-trainer build из папки задания, когда репозиторий уже лежит в src/repo.
+# Из него делается «удалённый» репозиторий, в котором есть коммит, которого нет у студента
+set -euo pipefail
+
+rm -rf src/just-push-it-origin.git
+
+TMP_REMOTE_WORK="$(mktemp -d /tmp/git-trainer_just-push-it-remote.XXXXXX)"
+trap 'rm -rf "$TMP_REMOTE_WORK"' EXIT
+
+git clone --quiet --bare src/repo src/just-push-it-origin.git
+rm -rf src/repo
+git clone --quiet src/just-push-it-origin.git src/repo
+git clone --quiet src/just-push-it-origin.git "$TMP_REMOTE_WORK"
+
+cp src/README.md "$TMP_REMOTE_WORK/README.md"
+
+(
+    cd "$TMP_REMOTE_WORK"
+    git config user.name "Razrushitell2005"
+    git config user.email "razrushitell2005@aboba.org"
+    git add README.md
+    GIT_AUTHOR_DATE="2026-03-24T12:00:00+04:00" \
+    GIT_COMMITTER_DATE="2026-03-24T12:00:00+04:00" \
+    git commit --quiet -m "docs: перевести README на русский язык"
+    git push --quiet origin main
+)
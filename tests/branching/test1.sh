@@ -5,9 +5,9 @@ cd "$HOME"
 cd counting-sort 2>/dev/null
 
 if [ "$?" -eq "0" ]; then
-	echo "1. Директория counting-sort существует."
+	echo 'git-trainer-report: {"status": "passed", "message": {"ru": "1. Директория counting-sort существует.", "en": "1. The counting-sort directory exists."}}'
 	exit 0
 else 
-	echo "1. Убедитесь, что директория counting-sort существует."
+	echo 'git-trainer-report: {"status": "failed", "message": {"ru": "1. Убедитесь, что директория counting-sort существует.", "en": "1. Make sure the counting-sort directory exists."}}'
 	exit 1
 fi
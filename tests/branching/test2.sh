@@ -5,9 +5,9 @@ cd "$HOME"
 cd counting-sort && git status &>/dev/null
 
 if [ "$?" -eq 0 ]; then
-	echo "2. Git-репозиторий существует."
+	echo 'git-trainer-report: {"status": "passed", "message": {"ru": "2. Git-репозиторий существует.", "en": "2. The Git repository exists."}}'
 	exit 0
 else 
-	echo "2. Убедитесь, что в директории counting-sort существует Git-репозиторий."
+	echo 'git-trainer-report: {"status": "failed", "message": {"ru": "2. Убедитесь, что в директории counting-sort существует Git-репозиторий.", "en": "2. Make sure there is a Git repository in the counting-sort directory."}}'
 	exit 1
 fi
@@ -9,10 +9,10 @@ sudo git rev-parse --verify print_vector &>/dev/null
 
 if [ "$?" -eq 0 ]; then
     sudo rm -rf "$NEW_DIR"
-	echo "3. Ветка print_vector существует." 
+	echo 'git-trainer-report: {"status": "passed", "message": {"ru": "3. Ветка print_vector существует.", "en": "3. The print_vector branch exists."}}'
 	exit 0
 else 
     sudo rm -rf "$NEW_DIR"
-	echo "3. Убедитесь, что ветка print_vector существует." 
+	echo 'git-trainer-report: {"status": "failed", "message": {"ru": "3. Убедитесь, что ветка print_vector существует.", "en": "3. Make sure the print_vector branch exists."}}'
 	exit 1
 fi
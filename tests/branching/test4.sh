@@ -10,10 +10,10 @@ sudo git switch print_vector &>/dev/null
 COMMIT_COUNT="$(git log --oneline 2>/dev/null | wc -l )"
 if [ "$COMMIT_COUNT" -ge "2" ]; then
     sudo rm -rf "$NEW_DIR"
-	echo "4. В новой ветке есть новые коммиты."
+	echo 'git-trainer-report: {"status": "passed", "message": {"ru": "4. В новой ветке есть новые коммиты.", "en": "4. The new branch has new commits."}}'
 	exit 0
 else 
     sudo rm -rf "$NEW_DIR"
-	echo "4. Убедитесь, что в новой ветке есть новые коммиты."
+	echo 'git-trainer-report: {"status": "failed", "message": {"ru": "4. Убедитесь, что в новой ветке есть новые коммиты.", "en": "4. Make sure the new branch has new commits."}}'
 	exit 1
 fi
@@ -4,9 +4,9 @@ cd "$HOME/counting-sort"
 
 if [ "$(git show-branch --merge-base print_vector remotes/origin/counting_sort)" = "790d2418057a3b0f0af1bd67d8704d1da92c179f" ]; then
 	
-    echo "5. Ветка print_vector создана от main."
+    echo 'git-trainer-report: {"status": "passed", "message": {"ru": "5. Ветка print_vector создана от main.", "en": "5. The print_vector branch was created from main."}}'
     exit 0
 else
-    echo "5. Убедитесь, что ветка print_vector создана от main."
+    echo 'git-trainer-report: {"status": "failed", "message": {"ru": "5. Убедитесь, что ветка print_vector создана от main.", "en": "5. Make sure the print_vector branch is created from main."}}'
     exit 1
 fi
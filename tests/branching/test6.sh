@@ -7,9 +7,9 @@ git merge-base --is-ancestor print_vector main
 CMD2="$(echo $?)"
 
 if [[ "$CMD1" = 0 && "$CMD2" = 0 ]]; then
-    echo "6. Все ветки были слиты в main."
+    echo 'git-trainer-report: {"status": "passed", "message": {"ru": "6. Все ветки были слиты в main.", "en": "6. All branches were merged into main."}}'
     exit 0
 else
-    echo "6. Убедитесь, что все ветки слиты в main." 
+    echo 'git-trainer-report: {"status": "failed", "message": {"ru": "6. Убедитесь, что все ветки слиты в main.", "en": "6. Make sure all branches are merged into main."}}'
     exit 1
 fi
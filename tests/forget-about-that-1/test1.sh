@@ -3,9 +3,9 @@
 REPO_DIR="$HOME/forget-about-that"
 
 if [ -d "$REPO_DIR" ]; then
-    echo "1. Директория forget-about-that существует."
+    echo 'git-trainer-report: {"status": "passed", "message": {"ru": "1. Директория forget-about-that существует.", "en": "1. The forget-about-that directory exists."}}'
     exit 0
 else
-    echo "1. Убедитесь, что директория forget-about-that существует."
+    echo 'git-trainer-report: {"status": "failed", "message": {"ru": "1. Убедитесь, что директория forget-about-that существует.", "en": "1. Make sure the forget-about-that directory exists."}}'
     exit 1
 fi
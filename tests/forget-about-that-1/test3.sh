@@ -3,9 +3,9 @@
 REPO_DIR="$HOME/forget-about-that"
 
 if [ -f "$REPO_DIR/.env" ]; then
-    echo "3. Файл .env остался в файловой системе."
+    echo 'git-trainer-report: {"status": "passed", "message": {"ru": "3. Файл .env остался в файловой системе.", "en": "3. The .env file is still in the file system."}}'
     exit 0
 else
-    echo "3. Убедитесь, что файл .env существует в файловой системе."
+    echo 'git-trainer-report: {"status": "failed", "message": {"ru": "3. Убедитесь, что файл .env существует в файловой системе.", "en": "3. Make sure the .env file exists in the file system."}}'
     exit 1
 fi
@@ -5,16 +5,16 @@ TMP_DIR="$(mktemp -d /tmp/git-trainer.XXXXXX)"
 trap 'rm -rf "$TMP_DIR"' EXIT
 
 if ! cp -R "$REPO_DIR" "$TMP_DIR/repo" 2>/dev/null; then
-    echo "4. Не удалось подготовить временную копию репозитория для проверки."
+    echo 'git-trainer-report: {"status": "failed", "message": {"ru": "4. Не удалось подготовить временную копию репозитория для проверки.", "en": "4. Failed to prepare a temporary copy of the repository for the check."}}'
     exit 1
 fi
 
 cd "$TMP_DIR/repo" || exit 1
 
 if git ls-files --error-unmatch .env >/dev/null 2>&1; then
-    echo "4. Убедитесь, что .env больше не отслеживается Git."
+    echo 'git-trainer-report: {"status": "failed", "message": {"ru": "4. Убедитесь, что .env больше не отслеживается Git.", "en": "4. Make sure .env is no longer tracked by Git."}}'
     exit 1
 else
-    echo "4. Файл .env больше не отслеживается Git."
+    echo 'git-trainer-report: {"status": "passed", "message": {"ru": "4. Файл .env больше не отслеживается Git.", "en": "4. The .env file is no longer tracked by Git."}}'
     exit 0
 fi
@@ -5,14 +5,14 @@ TMP_DIR="$(mktemp -d /tmp/git-trainer.XXXXXX)"
 trap 'rm -rf "$TMP_DIR"' EXIT
 
 if ! cp -R "$REPO_DIR" "$TMP_DIR/repo" 2>/dev/null; then
-    echo "5. Не удалось подготовить временную копию репозитория для проверки."
+    echo 'git-trainer-report: {"status": "failed", "message": {"ru": "5. Не удалось подготовить временную копию репозитория для проверки.", "en": "5. Failed to prepare a temporary copy of the repository for the check."}}'
     exit 1
 fi
 
 cd "$TMP_DIR/repo" || exit 1
 
 if [ ! -f .gitignore ]; then
-    echo "5. Убедитесь, что файл .gitignore существует и содержит .env."
+    echo 'git-trainer-report: {"status": "failed", "message": {"ru": "5. Убедитесь, что файл .gitignore существует и содержит .env.", "en": "5. Make sure the .gitignore file exists and contains .env."}}'
     exit 1
 fi
 
@@ -25,9 +25,9 @@ git check-ignore -q .env
 IS_IGNORED="$?"
 
 if [ "$HAS_EXACT_LINE" -eq 0 ] && [ "$IS_IGNORED" -eq 0 ]; then
-    echo "5. Файл .env добавлен в .gitignore и корректно игнорируется."
+    echo 'git-trainer-report: {"status": "passed", "message": {"ru": "5. Файл .env добавлен в .gitignore и корректно игнорируется.", "en": "5. The .env file is added to .gitignore and is ignored correctly."}}'
     exit 0
 else
-    echo "5. Убедитесь, что .env добавлен именно в .gitignore и реально игнорируется Git."
+    echo 'git-trainer-report: {"status": "failed", "message": {"ru": "5. Убедитесь, что .env добавлен именно в .gitignore и реально игнорируется Git.", "en": "5. Make sure .env is added to .gitignore itself and is actually ignored by Git."}}'
     exit 1
 fi
@@ -5,16 +5,16 @@ TMP_DIR="$(mktemp -d /tmp/git-trainer.XXXXXX)"
 trap 'rm -rf "$TMP_DIR"' EXIT
 
 if ! cp -R "$REPO_DIR" "$TMP_DIR/repo" 2>/dev/null; then
-    echo "6. Не удалось подготовить временную копию репозитория для проверки."
+    echo 'git-trainer-report: {"status": "failed", "message": {"ru": "6. Не удалось подготовить временную копию репозитория для проверки.", "en": "6. Failed to prepare a temporary copy of the repository for the check."}}'
     exit 1
 fi
 
 cd "$TMP_DIR/repo" || exit 1
 
 if git cat-file -e HEAD:.env 2>/dev/null; then
-    echo "6. Убедитесь, что в последнем коммите файла .env больше нет."
+    echo 'git-trainer-report: {"status": "failed", "message": {"ru": "6. Убедитесь, что в последнем коммите файла .env больше нет.", "en": "6. Make sure the last commit no longer contains the .env file."}}'
     exit 1
 else
-    echo "6. В последнем коммите файла .env нет."
+    echo 'git-trainer-report: {"status": "passed", "message": {"ru": "6. В последнем коммите файла .env нет.", "en": "6. The last commit does not contain the .env file."}}'
     exit 0
 fi
@@ -5,16 +5,16 @@ TMP_DIR="$(mktemp -d /tmp/git-trainer.XXXXXX)"
 trap 'rm -rf "$TMP_DIR"' EXIT
 
 if ! cp -R "$REPO_DIR" "$TMP_DIR/repo" 2>/dev/null; then
-    echo "7. Не удалось подготовить временную копию репозитория для проверки."
+    echo 'git-trainer-report: {"status": "failed", "message": {"ru": "7. Не удалось подготовить временную копию репозитория для проверки.", "en": "7. Failed to prepare a temporary copy of the repository for the check."}}'
     exit 1
 fi
 
 cd "$TMP_DIR/repo" || exit 1
 
 if git log HEAD --format=%H -- .env | grep -q .; then
-    echo "7. Убедитесь, что .env удалён из истории текущей ветки, а не только из рабочего дерева."
+    echo 'git-trainer-report: {"status": "failed", "message": {"ru": "7. Убедитесь, что .env удалён из истории текущей ветки, а не только из рабочего дерева.", "en": "7. Make sure .env is removed from the history of the current branch, not only from the working tree."}}'
     exit 1
 else
-    echo "7. Файла .env нет в истории текущей ветки."
+    echo 'git-trainer-report: {"status": "passed", "message": {"ru": "7. Файла .env нет в истории текущей ветки.", "en": "7. The .env file is not in the history of the current branch."}}'
     exit 0
 fi
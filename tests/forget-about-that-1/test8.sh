@@ -9,7 +9,7 @@ EXPECTED_PARENT_HASH="d6086d04bac7a1efd3705bfd513c1db0e82ea721"
 EXPECTED_COMMIT_MESSAGE=$'feat: Make app configurable'
 
 if ! cp -R "$REPO_DIR" "$TMP_DIR/repo" 2>/dev/null; then
-    echo "8. Не удалось подготовить временную копию репозитория для проверки."
+    echo 'git-trainer-report: {"status": "failed", "message": {"ru": "8. Не удалось подготовить временную копию репозитория для проверки.", "en": "8. Failed to prepare a temporary copy of the repository for the check."}}'
     exit 1
 fi
 
@@ -22,9 +22,9 @@ ACTUAL_COMMIT_MESSAGE="$(git log -1 --format=%B 2>/dev/null)"
 if [ "$ACTUAL_COMMIT_COUNT" = "$EXPECTED_COMMIT_COUNT" ] \
    && [ "$ACTUAL_PARENT_HASH" = "$EXPECTED_PARENT_HASH" ] \
    && [ "$ACTUAL_COMMIT_MESSAGE" = "$EXPECTED_COMMIT_MESSAGE" ]; then
-    echo "8. Последний коммит был переписан без добавления нового коммита и без изменения текста сообщения."
+    echo 'git-trainer-report: {"status": "passed", "message": {"ru": "8. Последний коммит был переписан без добавления нового коммита и без изменения текста сообщения.", "en": "8. The last commit was rewritten without adding a new commit or changing its message."}}'
     exit 0
 else
-    echo "8. Убедитесь, что вы не создали новый коммит и не изменили текст сообщения последнего коммита."
+    echo 'git-trainer-report: {"status": "failed", "message": {"ru": "8. Убедитесь, что вы не создали новый коммит и не изменили текст сообщения последнего коммита.", "en": "8. Make sure you did not create a new commit or change the message of the last commit."}}'
     exit 1
 fi
@@ -5,9 +5,9 @@ cd "$HOME"
 cd hello-world 2>/dev/null
 
 if [ "$?" -eq "0" ]; then
-	echo "1. Директория hello-world существует."
+	echo 'git-trainer-report: {"status": "passed", "message": {"ru": "1. Директория hello-world существует.", "en": "1. The hello-world directory exists."}}'
 	exit 0
 else 
-	echo "1. Убедитесь, что директория hello-world существует."
+	echo 'git-trainer-report: {"status": "failed", "message": {"ru": "1. Убедитесь, что директория hello-world существует.", "en": "1. Make sure the hello-world directory exists."}}'
 	exit 1
 fi
@@ -4,9 +4,9 @@ cd "$HOME"
 
 COMMIT_COUNT="$(cd hello-world && git log --oneline 2>/dev/null | wc -l )"
 if [ "$COMMIT_COUNT" -eq "1" ]; then
-	echo "3. В репозитории ровно один коммит."
+	echo 'git-trainer-report: {"status": "passed", "message": {"ru": "3. В репозитории ровно один коммит.", "en": "3. The repository has exactly one commit."}}'
 	exit 0
 else 
-    echo "3. Убедитесь, что в репозитории ровно один коммит (найдено: $COMMIT_COUNT)."
+	echo "git-trainer-report: {\"status\": \"failed\", \"message\": {\"ru\": \"3. Убедитесь, что в репозитории ровно один коммит.\", \"en\": \"3. Make sure the repository has exactly one commit.\"}, \"expected\": \"1\", \"actual\": \"$COMMIT_COUNT\", \"hint\": {\"ru\": \"Посмотрите на вывод git log --oneline.\", \"en\": \"Look at the output of git log --oneline.\"}}"
 	exit 1
 fi
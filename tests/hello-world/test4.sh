@@ -1,14 +1,26 @@
 #!/bin/bash
 
+# Вывод студента попадает в отчёт строкой JSON, поэтому его нужно экранировать
+json_string() {
+    local text
+    text="$(printf '%s' "$1" | tr -d '\000-\010\013\014\016-\037')"
+    text="${text//\\/\\\\}"
+    text="${text//\"/\\\"}"
+    text="${text//$'\t'/\\t}"
+    text="${text//$'\r'/\\r}"
+    text="${text//$'\n'/\\n}"
+    printf '"%s"' "$text"
+}
+
 cd "$HOME"
 
 OUTPUT=$(cd hello-world && git log --oneline --format=%s)
 OUTPUT_EXPECTED="Initial commit"
 
 if echo "$OUTPUT" | grep -iq "^$OUTPUT_EXPECTED" ; then
-	echo "4. Коммит имеет название \"Initial commit\"."
+	echo 'git-trainer-report: {"status": "passed", "message": {"ru": "4. Коммит имеет название \"Initial commit\".", "en": "4. The commit is named \"Initial commit\"."}}'
 	exit 0
 else 
-    echo "4. Убедитесь, что коммит имеет название \"Initial commit\" (найдено: $OUTPUT)."
+    echo "git-trainer-report: {\"status\": \"failed\", \"message\": {\"ru\": \"4. Убедитесь, что коммит имеет название \\\"Initial commit\\\".\", \"en\": \"4. Make sure the commit is named \\\"Initial commit\\\".\"}, \"expected\": \"Initial commit\", \"actual\": $(json_string "$OUTPUT")}"
 	exit 1
 fi
@@ -6,9 +6,9 @@ OUTPUT=$(cd hello-world && git show --name-only --format="" HEAD)
 OUTPUT_EXPECTED="main.cpp"
 
 if echo "$OUTPUT" | grep -q "$OUTPUT_EXPECTED" ; then
-    echo "5. В коммите есть файл main.cpp."
+    echo 'git-trainer-report: {"status": "passed", "message": {"ru": "5. В коммите есть файл main.cpp.", "en": "5. The commit contains the main.cpp file."}}'
     exit 0
 else
-    echo "5. Убедитесь, что в коммите есть файл main.cpp."
+    echo 'git-trainer-report: {"status": "failed", "message": {"ru": "5. Убедитесь, что в коммите есть файл main.cpp.", "en": "5. Make sure the commit contains the main.cpp file."}}'
     exit 1
 fi
@@ -1,18 +1,30 @@
 #!/bin/bash
 
+# Вывод студента попадает в отчёт строкой JSON, поэтому его нужно экранировать
+json_string() {
+    local text
+    text="$(printf '%s' "$1" | tr -d '\000-\010\013\014\016-\037')"
+    text="${text//\\/\\\\}"
+    text="${text//\"/\\\"}"
+    text="${text//$'\t'/\\t}"
+    text="${text//$'\r'/\\r}"
+    text="${text//$'\n'/\\n}"
+    printf '"%s"' "$text"
+}
+
 NEW_DIR="/etc/git-trainer/hello-world"
 sudo cp -r "$HOME/hello-world" "$NEW_DIR"
 git config --global --add safe.directory "$NEW_DIR"
 cd "$NEW_DIR" 
 
-sudo g++ -o main main.cpp
+COMPILER_OUTPUT="$(sudo g++ -o main main.cpp 2>&1)"
 
 if [ "$?" -eq "0" ]; then 
-    echo "6. Файл main.cpp компилируется."
+    echo 'git-trainer-report: {"status": "passed", "message": {"ru": "6. Файл main.cpp компилируется.", "en": "6. The main.cpp file compiles."}}'
     sudo rm -rf "$NEW_DIR"
     exit 0
 else 
-    echo "6. Убедитесь, что файл main.cpp компилируется"
+    echo "git-trainer-report: {\"status\": \"failed\", \"message\": {\"ru\": \"6. Убедитесь, что файл main.cpp компилируется.\", \"en\": \"6. Make sure the main.cpp file compiles.\"}, \"actual\": $(json_string "$COMPILER_OUTPUT")}"
     sudo rm -rf "$NEW_DIR"
     exit 1
 fi
@@ -1,19 +1,31 @@
 #!/bin/bash
 
+# Вывод студента попадает в отчёт строкой JSON, поэтому его нужно экранировать
+json_string() {
+    local text
+    text="$(printf '%s' "$1" | tr -d '\000-\010\013\014\016-\037')"
+    text="${text//\\/\\\\}"
+    text="${text//\"/\\\"}"
+    text="${text//$'\t'/\\t}"
+    text="${text//$'\r'/\\r}"
+    text="${text//$'\n'/\\n}"
+    printf '"%s"' "$text"
+}
+
 NEW_DIR="/etc/git-trainer/hello-world"
 sudo cp -r "$HOME/hello-world" "$NEW_DIR"
 git config --global --add safe.directory "$NEW_DIR"
 cd "$NEW_DIR" 
 
-sudo g++ -o main main.cpp
+sudo g++ -o main main.cpp &>/dev/null
 
 OUTPUT="$(./main)"
 OUTPUT_EXPECTED="Hello, World!"
 
 if echo "$OUTPUT" | grep -iq "^$OUTPUT_EXPECTED" ; then
-    echo "7. Программа выводит \"Hello, World!\"."
+    echo 'git-trainer-report: {"status": "passed", "message": {"ru": "7. Программа выводит \"Hello, World!\".", "en": "7. The program prints \"Hello, World!\"."}}'
     exit 0
 else 
-    echo "7. Убедитесь, что программа выводит \"Hello, World!\" (найдено: $OUTPUT)."
+    echo "git-trainer-report: {\"status\": \"failed\", \"message\": {\"ru\": \"7. Убедитесь, что программа выводит \\\"Hello, World!\\\".\", \"en\": \"7. Make sure the program prints \\\"Hello, World!\\\".\"}, \"expected\": \"Hello, World!\", \"actual\": $(json_string "$OUTPUT")}"
     exit 1
 fi
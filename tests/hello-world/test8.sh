@@ -1,5 +1,17 @@
 #!/bin/bash
 
+# Вывод студента попадает в отчёт строкой JSON, поэтому его нужно экранировать
+json_string() {
+    local text
+    text="$(printf '%s' "$1" | tr -d '\000-\010\013\014\016-\037')"
+    text="${text//\\/\\\\}"
+    text="${text//\"/\\\"}"
+    text="${text//$'\t'/\\t}"
+    text="${text//$'\r'/\\r}"
+    text="${text//$'\n'/\\n}"
+    printf '"%s"' "$text"
+}
+
 NEW_DIR="/etc/git-trainer/hello-world"
 sudo cp -r "$HOME/hello-world/" "$NEW_DIR"
 git config --global --add safe.directory "$NEW_DIR"
@@ -9,10 +21,10 @@ sudo git reset --hard HEAD &> /dev/null && sudo git clean -fdx &> /dev/null
 EXEC_FILES=$(find . -maxdepth 1 -type f -executable)
 if [ -n "$EXEC_FILES" ]; then
     sudo rm -rf "$NEW_DIR"
-    echo "8. В коммите найдены исполняемые файлы: $EXEC_FILES"
+    echo "git-trainer-report: {\"status\": \"failed\", \"message\": {\"ru\": \"8. Убедитесь, что в коммите нет исполняемых файлов.\", \"en\": \"8. Make sure the commit has no executable files.\"}, \"actual\": $(json_string "$EXEC_FILES")}"
     exit 1
 else
     sudo rm -rf "$NEW_DIR"
-    echo "8. Исполняемые файлы в коммите не найдены."
+    echo 'git-trainer-report: {"status": "passed", "message": {"ru": "8. Исполняемые файлы в коммите не найдены.", "en": "8. No executable files were found in the commit."}}'
     exit 0
 fi
@@ -3,9 +3,9 @@
 REPO_DIR="$HOME/just-push-it"
 
 if [ -d "$REPO_DIR" ]; then
-    echo "1. Директория just-push-it существует."
+    echo 'git-trainer-report: {"status": "passed", "message": {"ru": "1. Директория just-push-it существует.", "en": "1. The just-push-it directory exists."}}'
     exit 0
 else
-    echo "1. Убедитесь, что директория just-push-it существует."
+    echo 'git-trainer-report: {"status": "failed", "message": {"ru": "1. Убедитесь, что директория just-push-it существует.", "en": "1. Make sure the just-push-it directory exists."}}'
     exit 1
 fi
@@ -3,9 +3,9 @@
 REPO_DIR="$HOME/just-push-it"
 
 if [ -d "$REPO_DIR/.git" ] && git -C "$REPO_DIR" status &>/dev/null; then
-    echo "2. Git-репозиторий существует."
+    echo 'git-trainer-report: {"status": "passed", "message": {"ru": "2. Git-репозиторий существует.", "en": "2. The Git repository exists."}}'
     exit 0
 else
-    echo "2. Убедитесь, что в директории just-push-it существует Git-репозиторий."
+    echo 'git-trainer-report: {"status": "failed", "message": {"ru": "2. Убедитесь, что в директории just-push-it существует Git-репозиторий.", "en": "2. Make sure there is a Git repository in the just-push-it directory."}}'
     exit 1
 fi
@@ -5,7 +5,7 @@ TMP_DIR="$(mktemp -d /tmp/git-trainer.XXXXXX)"
 trap 'rm -rf "$TMP_DIR"' EXIT
 
 if ! cp -R "$REPO_DIR" "$TMP_DIR/repo" 2>/dev/null; then
-    echo "3. Произошла системная ошибка, сообщите о ней преподавателю."
+    echo 'git-trainer-report: {"status": "failed", "message": {"ru": "3. Произошла системная ошибка, сообщите о ней преподавателю.", "en": "3. A system error occurred, please report it to your teacher."}}'
     exit 1
 fi
 
@@ -14,9 +14,9 @@ git reset --hard HEAD &>/dev/null
 git clean -fdx &>/dev/null
 
 if g++ main.cpp -o just-push-it &>/dev/null; then
-    echo "3. Программа компилируется."
+    echo 'git-trainer-report: {"status": "passed", "message": {"ru": "3. Программа компилируется.", "en": "3. The program compiles."}}'
     exit 0
 else
-    echo "3. Убедитесь, что main.cpp компилируется."
+    echo 'git-trainer-report: {"status": "failed", "message": {"ru": "3. Убедитесь, что main.cpp компилируется.", "en": "3. Make sure main.cpp compiles."}}'
     exit 1
 fi
@@ -1,5 +1,17 @@
 #!/bin/bash
 
+# Вывод студента попадает в отчёт строкой JSON, поэтому его нужно экранировать
+json_string() {
+    local text
+    text="$(printf '%s' "$1" | tr -d '\000-\010\013\014\016-\037')"
+    text="${text//\\/\\\\}"
+    text="${text//\"/\\\"}"
+    text="${text//$'\t'/\\t}"
+    text="${text//$'\r'/\\r}"
+    text="${text//$'\n'/\\n}"
+    printf '"%s"' "$text"
+}
+
 REPO_DIR="$HOME/just-push-it"
 TMP_DIR="$(mktemp -d /tmp/git-trainer.XXXXXX)"
 trap 'rm -rf "$TMP_DIR"' EXIT
@@ -7,7 +19,7 @@ trap 'rm -rf "$TMP_DIR"' EXIT
 EXPECTED_OUTPUT=$'max(7, 3) = 7\nmax(-4, 2) = 2'
 
 if ! cp -R "$REPO_DIR" "$TMP_DIR/repo" 2>/dev/null; then
-    echo "4. Произошла системная ошибка, сообщите о ней преподавателю."
+    echo 'git-trainer-report: {"status": "failed", "message": {"ru": "4. Произошла системная ошибка, сообщите о ней преподавателю.", "en": "4. A system error occurred, please report it to your teacher."}}'
     exit 1
 fi
 
@@ -16,16 +28,16 @@ git reset --hard HEAD &>/dev/null
 git clean -fdx &>/dev/null
 
 if ! g++ main.cpp -o just-push-it &>/dev/null; then
-    echo "4. Не удалось скомпилировать программу для проверки результата."
+    echo 'git-trainer-report: {"status": "failed", "message": {"ru": "4. Не удалось скомпилировать программу для проверки результата.", "en": "4. Failed to compile the program to check its output."}}'
     exit 1
 fi
 
 ACTUAL_OUTPUT="$(./just-push-it)"
 
 if [ "$ACTUAL_OUTPUT" = "$EXPECTED_OUTPUT" ]; then
-    echo "4. Программа работает корректно."
+    echo 'git-trainer-report: {"status": "passed", "message": {"ru": "4. Программа работает корректно.", "en": "4. The program works correctly."}}'
     exit 0
 else
-    echo "4. Убедитесь, что max_of_two реализована правильно (получено: $ACTUAL_OUTPUT)."
+    echo "git-trainer-report: {\"status\": \"failed\", \"message\": {\"ru\": \"4. Убедитесь, что max_of_two реализована правильно.\", \"en\": \"4. Make sure max_of_two is implemented correctly.\"}, \"expected\": $(json_string "$EXPECTED_OUTPUT"), \"actual\": $(json_string "$ACTUAL_OUTPUT")}"
     exit 1
 fi
@@ -7,9 +7,9 @@ LOCAL_HEAD="$(git -C "$REPO_DIR" rev-parse HEAD 2>/dev/null)"
 REMOTE_HEAD="$(git --git-dir="$REMOTE_GIT_DIR" rev-parse main 2>/dev/null)"
 
 if [ -n "$LOCAL_HEAD" ] && [ "$LOCAL_HEAD" = "$REMOTE_HEAD" ]; then
-    echo "5. Последний локальный коммит отправлен в origin."
+    echo 'git-trainer-report: {"status": "passed", "message": {"ru": "5. Последний локальный коммит отправлен в origin.", "en": "5. The last local commit is pushed to origin."}}'
     exit 0
 else
-    echo "5. Убедитесь, что вы запушили итоговый коммит в origin/main."
+    echo 'git-trainer-report: {"status": "failed", "message": {"ru": "5. Убедитесь, что вы запушили итоговый коммит в origin/main.", "en": "5. Make sure you pushed the final commit to origin/main."}}'
     exit 1
 fi
@@ -5,7 +5,7 @@ TMP_DIR="$(mktemp -d /tmp/git-trainer.XXXXXX)"
 trap 'rm -rf "$TMP_DIR"' EXIT
 
 if ! cp -R "$REPO_DIR" "$TMP_DIR/repo" 2>/dev/null; then
-    echo "6. Произошла системная ошибка, сообщите о ней преподавателю."
+    echo 'git-trainer-report: {"status": "failed", "message": {"ru": "6. Произошла системная ошибка, сообщите о ней преподавателю.", "en": "6. A system error occurred, please report it to your teacher."}}'
     exit 1
 fi
 
@@ -15,9 +15,9 @@ git clean -fdx &>/dev/null
 
 if grep -qxF '# Max Of Two' README.md >/dev/null 2>&1 && \
    grep -qxF 'Очень полезная программа, находящая максимум из двух чисел.' README.md >/dev/null 2>&1; then
-    echo "6. README.md содержит перевод из удалённого репозитория."
+    echo 'git-trainer-report: {"status": "passed", "message": {"ru": "6. README.md содержит перевод из удалённого репозитория.", "en": "6. README.md contains the translation from the remote repository."}}'
     exit 0
 else
-    echo "6. Убедитесь, что вы забрали удалённый коммит с переводом README.md."
+    echo 'git-trainer-report: {"status": "failed", "message": {"ru": "6. Убедитесь, что вы забрали удалённый коммит с переводом README.md.", "en": "6. Make sure you fetched the remote commit with the README.md translation."}}'
     exit 1
 fi
@@ -9,7 +9,7 @@ EXPECTED_ROOT_MESSAGE="chore: bootstrap just-push-it project"
 EXPECTED_REMOTE_MESSAGE="docs: перевести README на русский язык"
 
 if ! cp -R "$REPO_DIR" "$TMP_DIR/repo" 2>/dev/null; then
-    echo "7. Произошла системная ошибка, сообщите о ней преподавателю."
+    echo 'git-trainer-report: {"status": "failed", "message": {"ru": "7. Произошла системная ошибка, сообщите о ней преподавателю.", "en": "7. A system error occurred, please report it to your teacher."}}'
     exit 1
 fi
 
@@ -26,12 +26,12 @@ if [ "$ACTUAL_COMMIT_COUNT" = "$EXPECTED_COMMIT_COUNT" ] && \
    [ "$HEAD_PARENT_COUNT" = "1" ] && \
    [ "$PARENT_MESSAGE" = "$EXPECTED_REMOTE_MESSAGE" ] && \
    [ "$ROOT_MESSAGE" = "$EXPECTED_ROOT_MESSAGE" ]; then
-    echo "7. История линейная: сначала базовый коммит, затем удалённый коммит, затем ваш коммит."
+    echo 'git-trainer-report: {"status": "passed", "message": {"ru": "7. История линейная: сначала базовый коммит, затем удалённый коммит, затем ваш коммит.", "en": "7. The history is linear: the base commit, then the remote commit, then your commit."}}'
     exit 0
 elif [ "$ACTUAL_COMMIT_COUNT" != "$EXPECTED_COMMIT_COUNT" ]; then
-    echo "7. Убедитесь, что вы сделали ровно один коммит."
+    echo 'git-trainer-report: {"status": "failed", "message": {"ru": "7. Убедитесь, что вы сделали ровно один коммит.", "en": "7. Make sure you made exactly one commit."}}'
     exit 1
 else
-    echo "7. Убедитесь, что вы подтянули удалённый коммит через rebase и не создали merge-коммит."
+    echo 'git-trainer-report: {"status": "failed", "message": {"ru": "7. Убедитесь, что вы подтянули удалённый коммит через rebase и не создали merge-коммит.", "en": "7. Make sure you pulled the remote commit with a rebase and did not create a merge commit."}}'
     exit 1
 fi
@@ -5,9 +5,9 @@ cd "$HOME"
 cd binary-addition > /dev/null
 
 if [ "$?" -eq 0 ]; then
-	echo "1. Директория binary-addition существует." 
+	echo 'git-trainer-report: {"status": "passed", "message": {"ru": "1. Директория binary-addition существует.", "en": "1. The binary-addition directory exists."}}'
 	exit 0
 else 
-	echo "1. Убедитесь, что директория binary-addition существует."
+	echo 'git-trainer-report: {"status": "failed", "message": {"ru": "1. Убедитесь, что директория binary-addition существует.", "en": "1. Make sure the binary-addition directory exists."}}'
 	exit 1
 fi
@@ -8,11 +8,11 @@ cd "$NEW_DIR"
 OUT="$(git rev-parse gohy907/feats 2>/dev/null)"
 
 if [[ "$?" = 128 || "$OUT" = "8e203de56cd1581a1ba8f3b203c81763f3a8d700" ]]; then
-	echo "3. В ветке gohy907/feats нет новых коммитов." 
+	echo 'git-trainer-report: {"status": "passed", "message": {"ru": "3. В ветке gohy907/feats нет новых коммитов.", "en": "3. The gohy907/feats branch has no new commits."}}'
     sudo rm -rf "$NEW_DIR"
 	exit 0
 else 
-	echo "3. Убедитесь, что в ветке gohy907/feats нет новых коммитов."
+	echo 'git-trainer-report: {"status": "failed", "message": {"ru": "3. Убедитесь, что в ветке gohy907/feats нет новых коммитов.", "en": "3. Make sure the gohy907/feats branch has no new commits."}}'
     sudo rm -rf "$NEW_DIR"
 	exit 1
 fi
@@ -8,11 +8,11 @@ cd "$NEW_DIR"
 OUT="$(git rev-parse slim-s/feats 2>/dev/null)"
 
 if [[ "$?" = 128 || "$OUT" = "4113823c64d59a05f8de55ff36f74230e033bc68" ]]; then
-	echo "4. В ветке slim-s/feats нет новых коммитов." 
+	echo 'git-trainer-report: {"status": "passed", "message": {"ru": "4. В ветке slim-s/feats нет новых коммитов.", "en": "4. The slim-s/feats branch has no new commits."}}'
     sudo rm -rf "$NEW_DIR"
 	exit 0
 else 
-	echo "4. Убедитесь, что в ветке slim-s/feats нет новых коммитов."
+	echo 'git-trainer-report: {"status": "failed", "message": {"ru": "4. Убедитесь, что в ветке slim-s/feats нет новых коммитов.", "en": "4. Make sure the slim-s/feats branch has no new commits."}}'
     sudo rm -rf "$NEW_DIR"
 	exit 1
 fi
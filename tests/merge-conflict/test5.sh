@@ -11,11 +11,11 @@ git merge-base --is-ancestor remotes/origin/gohy907/feats main &> /dev/null
 CMD2="$(echo $?)"
 
 if [[ "$CMD1" = 0 && "$CMD2" = 0 ]]; then
-    echo "5. Все ветки были соединены в main."
+    echo 'git-trainer-report: {"status": "passed", "message": {"ru": "5. Все ветки были соединены в main.", "en": "5. All branches were merged into main."}}'
     sudo rm -rf "$NEW_DIR"
     exit 0
 else
-    echo "5. Убедитесь, что все ветки соединены в main." 
+    echo 'git-trainer-report: {"status": "failed", "message": {"ru": "5. Убедитесь, что все ветки соединены в main.", "en": "5. Make sure all branches are merged into main."}}'
     sudo rm -rf "$NEW_DIR"
     exit 1
 fi
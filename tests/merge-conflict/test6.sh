@@ -7,9 +7,9 @@ git switch main &>/dev/null
 grep "<<<<<<<" main.cpp &>/dev/null && grep ">>>>>>>" main.cpp &>/dev/null
 
 if [ "$?" = 1 ]; then
-    echo "6. Merge-конфликт решён."
+    echo 'git-trainer-report: {"status": "passed", "message": {"ru": "6. Merge-конфликт решён.", "en": "6. The merge conflict is resolved."}}'
     exit 0
 else
-    echo "6. Убедитесь, что Merge-конфликт решён."
+    echo 'git-trainer-report: {"status": "failed", "message": {"ru": "6. Убедитесь, что Merge-конфликт решён.", "en": "6. Make sure the merge conflict is resolved."}}'
     exit 1
 fi
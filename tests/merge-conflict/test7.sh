@@ -1,16 +1,28 @@
 #!/bin/bash
 
+# Вывод студента попадает в отчёт строкой JSON, поэтому его нужно экранировать
+json_string() {
+    local text
+    text="$(printf '%s' "$1" | tr -d '\000-\010\013\014\016-\037')"
+    text="${text//\\/\\\\}"
+    text="${text//\"/\\\"}"
+    text="${text//$'\t'/\\t}"
+    text="${text//$'\r'/\\r}"
+    text="${text//$'\n'/\\n}"
+    printf '"%s"' "$text"
+}
+
 NEW_DIR="/etc/git-trainer/binary-addition"
 sudo cp -r "$HOME/binary-addition" "$NEW_DIR"
 git config --global --add safe.directory "$NEW_DIR"
 cd "$NEW_DIR" 
 
-sudo g++ main.cpp -o main
+COMPILER_OUTPUT="$(sudo g++ main.cpp -o main 2>&1)"
 
 if [ "$?" -eq 0 ]; then
-    echo "7. Программа компилируется."
+    echo 'git-trainer-report: {"status": "passed", "message": {"ru": "7. Программа компилируется.", "en": "7. The program compiles."}}'
     exit 0
 else
-    echo "7. Убедитесь, что программа компилируется." 
+    echo "git-trainer-report: {\"status\": \"failed\", \"message\": {\"ru\": \"7. Убедитесь, что программа компилируется.\", \"en\": \"7. Make sure the program compiles.\"}, \"actual\": $(json_string "$COMPILER_OUTPUT")}"
     exit 1
 fi
@@ -3,9 +3,9 @@
 REPO_DIR="$HOME/coffee-counter"
 
 if [ -d "$REPO_DIR" ]; then
-    echo "1. Директория coffee-counter существует."
+    echo 'git-trainer-report: {"status": "passed", "message": {"ru": "1. Директория coffee-counter существует.", "en": "1. The coffee-counter directory exists."}}'
     exit 0
 else
-    echo "1. Убедитесь, что директория coffee-counter существует."
+    echo 'git-trainer-report: {"status": "failed", "message": {"ru": "1. Убедитесь, что директория coffee-counter существует.", "en": "1. Make sure the coffee-counter directory exists."}}'
     exit 1
 fi
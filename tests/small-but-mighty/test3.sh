@@ -4,9 +4,9 @@ REPO_DIR="$HOME/coffee-counter"
 CURRENT_BRANCH="$(git -C "$REPO_DIR" branch --show-current 2>/dev/null)"
 
 if [ "$CURRENT_BRANCH" = "feature" ]; then
-    echo "3. В конце работы активна ветка feature."
+    echo 'git-trainer-report: {"status": "passed", "message": {"ru": "3. В конце работы активна ветка feature.", "en": "3. The feature branch is checked out at the end."}}'
     exit 0
 else
-    echo "3. Убедитесь, что после срочного фикса вы вернулись в ветку feature."
+    echo 'git-trainer-report: {"status": "failed", "message": {"ru": "3. Убедитесь, что после срочного фикса вы вернулись в ветку feature.", "en": "3. Make sure you returned to the feature branch after the hotfix."}}'
     exit 1
 fi
@@ -5,16 +5,16 @@ TMP_DIR="$(mktemp -d /tmp/git-trainer.XXXXXX)"
 trap 'rm -rf "$TMP_DIR"' EXIT
 
 if ! cp -R "$REPO_DIR" "$TMP_DIR/repo" 2>/dev/null; then
-    echo "4. Произошла системная ошибка, сообщите о ней преподавателю."
+    echo 'git-trainer-report: {"status": "failed", "message": {"ru": "4. Произошла системная ошибка, сообщите о ней преподавателю.", "en": "4. A system error occurred, please report it to your teacher."}}'
     exit 1
 fi
 
 cd "$TMP_DIR/repo" || exit 1
 
 if ! git diff --quiet || ! git diff --cached --quiet; then
-    echo "4. В ветке feature остались незакоммиченные изменения."
+    echo 'git-trainer-report: {"status": "passed", "message": {"ru": "4. В ветке feature остались незакоммиченные изменения.", "en": "4. The feature branch still has uncommitted changes."}}'
     exit 0
 else
-    echo "4. Убедитесь, что после возвращения в feature ваша незавершённая работа снова появилась в рабочем дереве."
+    echo 'git-trainer-report: {"status": "failed", "message": {"ru": "4. Убедитесь, что после возвращения в feature ваша незавершённая работа снова появилась в рабочем дереве.", "en": "4. Make sure your unfinished work is back in the working tree after returning to feature."}}'
     exit 1
 fi
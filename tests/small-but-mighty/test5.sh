@@ -8,7 +8,7 @@ EXPECTED_MENU_HASH="3a132579f292eb95c22ed47a23e011cebd27a5864f6fede54d3130c474da
 EXPECTED_RECEIPT_HASH="3ca18caa54645bdbaa2af7b690c0d289e9bf04719f08aba7c03e5cac6012bfa8"
 
 if ! cp -R "$REPO_DIR" "$TMP_DIR/repo" 2>/dev/null; then
-    echo "5. Произошла системная ошибка, сообщите о ней преподавателю."
+    echo 'git-trainer-report: {"status": "failed", "message": {"ru": "5. Произошла системная ошибка, сообщите о ней преподавателю.", "en": "5. A system error occurred, please report it to your teacher."}}'
     exit 1
 fi
 
@@ -21,9 +21,9 @@ ACTUAL_RECEIPT_HASH="$(sha256sum receipt.cpp | awk '{print $1}')"
 if [ "$CHANGED_FILES" = $'menu.cpp\nreceipt.cpp' ] \
    && [ "$ACTUAL_MENU_HASH" = "$EXPECTED_MENU_HASH" ] \
    && [ "$ACTUAL_RECEIPT_HASH" = "$EXPECTED_RECEIPT_HASH" ]; then
-    echo "5. В ветке feature восстановлена именно незавершённая работа над menu.cpp и receipt.cpp."
+    echo 'git-trainer-report: {"status": "passed", "message": {"ru": "5. В ветке feature восстановлена именно незавершённая работа над menu.cpp и receipt.cpp.", "en": "5. The feature branch has exactly the unfinished work on menu.cpp and receipt.cpp restored."}}'
     exit 0
 else
-    echo "5. Убедитесь, что в feature вернулись исходные незакоммиченные изменения в menu.cpp и receipt.cpp."
+    echo 'git-trainer-report: {"status": "failed", "message": {"ru": "5. Убедитесь, что в feature вернулись исходные незакоммиченные изменения в menu.cpp и receipt.cpp.", "en": "5. Make sure the original uncommitted changes to menu.cpp and receipt.cpp are back in feature."}}'
     exit 1
 fi
@@ -7,7 +7,7 @@ trap 'rm -rf "$TMP_DIR"' EXIT
 EXPECTED_COMMIT_COUNT="2"
 
 if ! cp -R "$REPO_DIR" "$TMP_DIR/repo" 2>/dev/null; then
-    echo "6. Произошла системная ошибка, сообщите о ней преподавателю."
+    echo 'git-trainer-report: {"status": "failed", "message": {"ru": "6. Произошла системная ошибка, сообщите о ней преподавателю.", "en": "6. A system error occurred, please report it to your teacher."}}'
     exit 1
 fi
 
@@ -19,9 +19,9 @@ EXPECTED_PARENT_HASH="$(git rev-parse main^ 2>/dev/null)"
 
 if [ "$ACTUAL_COMMIT_COUNT" = "$EXPECTED_COMMIT_COUNT" ] \
    && [ "$ACTUAL_PARENT_HASH" = "$EXPECTED_PARENT_HASH" ]; then
-    echo "6. Ветка feature не получила новых коммитов."
+    echo 'git-trainer-report: {"status": "passed", "message": {"ru": "6. Ветка feature не получила новых коммитов.", "en": "6. The feature branch got no new commits."}}'
     exit 0
 else
-    echo "6. Убедитесь, что незавершённая работа в feature так и осталась без нового коммита."
+    echo 'git-trainer-report: {"status": "failed", "message": {"ru": "6. Убедитесь, что незавершённая работа в feature так и осталась без нового коммита.", "en": "6. Make sure the unfinished work in feature is still not committed."}}'
     exit 1
 fi
@@ -7,7 +7,7 @@ trap 'rm -rf "$TMP_DIR"' EXIT
 EXPECTED_COMMIT_COUNT="2"
 
 if ! cp -R "$REPO_DIR" "$TMP_DIR/repo" 2>/dev/null; then
-    echo "7. Произошла системная ошибка, сообщите о ней преподавателю."
+    echo 'git-trainer-report: {"status": "failed", "message": {"ru": "7. Произошла системная ошибка, сообщите о ней преподавателю.", "en": "7. A system error occurred, please report it to your teacher."}}'
     exit 1
 fi
 
@@ -22,9 +22,9 @@ if [ "$ACTUAL_COMMIT_COUNT" = "$EXPECTED_COMMIT_COUNT" ] \
    && [ "$CHANGED_FILES" = "main.cpp" ] \
    && printf '%s' "$FIXED_MAIN_CONTENT" | grep -Fq 'Usage: ./coffee-counter <drink>' \
    && ! printf '%s' "$PREVIOUS_MAIN_CONTENT" | grep -Fq 'Usage: ./coffee-counter <drink>'; then
-    echo "7. Ветка main получила ровно один срочный фикс."
+    echo 'git-trainer-report: {"status": "passed", "message": {"ru": "7. Ветка main получила ровно один срочный фикс.", "en": "7. The main branch got exactly one hotfix."}}'
     exit 0
 else
-    echo "7. Убедитесь, что в main появился ровно один новый коммит."
+    echo 'git-trainer-report: {"status": "failed", "message": {"ru": "7. Убедитесь, что в main появился ровно один новый коммит.", "en": "7. Make sure main has exactly one new commit."}}'
     exit 1
 fi
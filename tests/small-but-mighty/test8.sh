@@ -1,5 +1,17 @@
 #!/bin/bash
 
+# Вывод студента попадает в отчёт строкой JSON, поэтому его нужно экранировать
+json_string() {
+    local text
+    text="$(printf '%s' "$1" | tr -d '\000-\010\013\014\016-\037')"
+    text="${text//\\/\\\\}"
+    text="${text//\"/\\\"}"
+    text="${text//$'\t'/\\t}"
+    text="${text//$'\r'/\\r}"
+    text="${text//$'\n'/\\n}"
+    printf '"%s"' "$text"
+}
+
 REPO_DIR="$HOME/coffee-counter"
 TMP_DIR="$(mktemp -d /tmp/git-trainer.XXXXXX)"
 RUN_DIR="$(mktemp -d /tmp/git-trainer-run.XXXXXX)"
@@ -8,7 +20,7 @@ trap 'rm -rf "$TMP_DIR" "$RUN_DIR"' EXIT
 EXPECTED_OUTPUT="Usage: ./coffee-counter <drink>"
 
 if ! cp -R "$REPO_DIR" "$TMP_DIR/repo" 2>/dev/null; then
-    echo "8. Произошла системная ошибка, сообщите о ней преподавателю."
+    echo 'git-trainer-report: {"status": "failed", "message": {"ru": "8. Произошла системная ошибка, сообщите о ней преподавателю.", "en": "8. A system error occurred, please report it to your teacher."}}'
     exit 1
 fi
 
@@ -16,8 +28,8 @@ cd "$TMP_DIR/repo" || exit 1
 
 git archive main | tar -x -C "$RUN_DIR"
 
-if ! (cd "$RUN_DIR" && g++ -std=c++17 main.cpp menu.cpp receipt.cpp -o coffee-counter); then
-    echo "8. Не удалось собрать программу из ветки main."
+if ! COMPILER_OUTPUT="$(cd "$RUN_DIR" && g++ -std=c++17 main.cpp menu.cpp receipt.cpp -o coffee-counter 2>&1)"; then
+    echo "git-trainer-report: {\"status\": \"failed\", \"message\": {\"ru\": \"8. Не удалось собрать программу из ветки main.\", \"en\": \"8. Failed to build the program from the main branch.\"}, \"actual\": $(json_string "$COMPILER_OUTPUT")}"
     exit 1
 fi
 
@@ -25,9 +37,9 @@ OUTPUT="$(cd "$RUN_DIR" && ./coffee-counter 2>&1)"
 EXIT_CODE="$?"
 
 if [ "$EXIT_CODE" = "1" ] && [ "$OUTPUT" = "$EXPECTED_OUTPUT" ]; then
-    echo "8. Срочный фикс в main корректно обрабатывает запуск без аргументов."
+    echo 'git-trainer-report: {"status": "passed", "message": {"ru": "8. Срочный фикс в main корректно обрабатывает запуск без аргументов.", "en": "8. The hotfix in main handles running without arguments correctly."}}'
     exit 0
 else
-    echo "8. Убедитесь, что в main программа без аргументов печатает \"Usage: ./coffee-counter <drink>\" и завершается с кодом 1."
+    echo 'git-trainer-report: {"status": "failed", "message": {"ru": "8. Убедитесь, что в main программа без аргументов печатает \"Usage: ./coffee-counter <drink>\" и завершается с кодом 1.", "en": "8. Make sure the program in main prints \"Usage: ./coffee-counter <drink>\" and exits with code 1 when run without arguments."}}'
     exit 1
 fi
@@ -0,0 +1,104 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Генерирует embedded.rs со схемой базы данных, миграциями, манифестами заданий и каталогами сообщений,
+// чтобы бинарник не зависел от файлов рядом с собой.
+fn main() {
+    let root = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap())
+        .parent()
+        .unwrap()
+        .to_path_buf();
+    let out = PathBuf::from(env::var("OUT_DIR").unwrap()).join("embedded.rs");
+
+    let schema = root.join("schema.sql");
+    let migrations_dir = root.join("migrations");
+    let tasks_dir = root.join("tasks");
+    let locales_dir = root.join("locales");
+
+    println!("cargo:rerun-if-changed={}", schema.display());
+    println!("cargo:rerun-if-changed={}", migrations_dir.display());
+    println!("cargo:rerun-if-changed={}", tasks_dir.display());
+    println!("cargo:rerun-if-changed={}", locales_dir.display());
+
+    let mut code = String::new();
+    code.push_str(&format!(
+        "pub static SCHEMA: &str = include_str!({:?});\n\n",
+        schema.display().to_string()
+    ));
+
+    code.push_str("pub static MIGRATIONS: &[EmbeddedMigration] = &[\n");
+    for dir in sorted_subdirs(&migrations_dir) {
+        let up = dir.join("up.sql");
+        if !up.is_file() {
+            continue;
+        }
+        println!("cargo:rerun-if-changed={}", dir.display());
+        let down = dir.join("down.sql");
+        let down = if down.is_file() {
+            format!("Some(include_str!({:?}))", down.display().to_string())
+        } else {
+            "None".to_string()
+        };
+        code.push_str(&format!(
+            "    EmbeddedMigration {{ name: {:?}, up: include_str!({:?}), down: {} }},\n",
+            dir.file_name().unwrap().to_string_lossy(),
+            up.display().to_string(),
+            down
+        ));
+    }
+    code.push_str("];\n\n");
+
+    code.push_str("pub static TASK_MANIFESTS: &[&str] = &[\n");
+    for dir in sorted_subdirs(&tasks_dir) {
+        let manifest = dir.join("task.toml");
+        if !manifest.is_file() {
+            continue;
+        }
+        println!("cargo:rerun-if-changed={}", manifest.display());
+        code.push_str(&format!(
+            "    include_str!({:?}),\n",
+            manifest.display().to_string()
+        ));
+    }
+    code.push_str("];\n\n");
+
+    code.push_str("pub static LOCALES: &[(&str, &str)] = &[\n");
+    for catalog in sorted_files(&locales_dir, "toml") {
+        println!("cargo:rerun-if-changed={}", catalog.display());
+        code.push_str(&format!(
+            "    ({:?}, include_str!({:?})),\n",
+            catalog.file_stem().unwrap().to_string_lossy(),
+            catalog.display().to_string()
+        ));
+    }
+    code.push_str("];\n");
+
+    fs::write(out, code).unwrap();
+}
+
+fn sorted_subdirs(dir: &Path) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = match fs::read_dir(dir) {
+        Ok(read_dir) => read_dir
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.is_dir())
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    dirs.sort();
+    dirs
+}
+
+fn sorted_files(dir: &Path, extension: &str) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = match fs::read_dir(dir) {
+        Ok(read_dir) => read_dir
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == extension))
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    files.sort();
+    files
+}
@@ -0,0 +1,220 @@
+use crate::db::Repo;
+use crate::error::AppError;
+use crate::images::{self, BuildEvent, BuildOptions};
+use crate::locale::{tr, tr_fmt};
+use crate::migrations::{MigrationError, MigrationState};
+use clap::{Arg, ArgAction, Command, CommandFactory, FromArgMatches, Parser, Subcommand};
+use std::path::PathBuf;
+use tokio::sync::mpsc;
+
+/// Help texts come from the locale catalog, see `localized_command`
+#[derive(Parser)]
+#[command(name = "git-trainer")]
+#[command(about = "git-trainer", long_about = None)]
+#[command(disable_help_subcommand = true)]
+#[command(disable_help_flag = true)]
+pub struct Args {
+    #[command(subcommand)]
+    pub command: Option<AdminCommand>,
+
+    /// Record task terminal sessions in the asciicast format (ui.record_sessions in the config)
+    #[arg(long)]
+    pub record: bool,
+
+    /// Lines of output kept for scrolling in the task terminal (ui.scrollback in the config)
+    #[arg(long)]
+    pub scrollback: Option<usize>,
+}
+
+#[derive(Subcommand)]
+pub enum AdminCommand {
+    /// Manage database migrations
+    Migrate {
+        #[command(subcommand)]
+        action: MigrateAction,
+    },
+
+    /// Build the base image and the task images, skipping up-to-date ones
+    Build {
+        /// Build only the image of this task (and the base image)
+        task: Option<String>,
+
+        /// git-trainer sources the images are built from (paths.build_context in the config)
+        #[arg(long)]
+        context: Option<PathBuf>,
+
+        /// Directory with task repositories by work_name, copied instead of cloned (paths.task_sources in the config)
+        #[arg(long)]
+        task_sources: Option<PathBuf>,
+
+        /// Rebuild up-to-date images too
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum MigrateAction {
+    /// Apply all pending migrations
+    Up {
+        /// Apply migrations only up to this version inclusive (e.g. 20260322000000)
+        #[arg(long)]
+        to: Option<i64>,
+    },
+
+    /// Revert the latest applied migrations
+    Down {
+        /// How many migrations to revert
+        #[arg(short = 'n', long, default_value_t = 1)]
+        steps: usize,
+    },
+
+    /// Show the state of migrations
+    Status,
+}
+
+/// Help of the command and of its subcommands in the language of the interface
+fn localized_command() -> Command {
+    let template = format!(
+        "{{about-with-newline}}\n{} {{usage}}\n\n{{all-args}}{{after-help}}",
+        tr("admin.usage_heading")
+    );
+    let localize_args = |command: Command| {
+        command.help_template(&template).mut_args(|arg| {
+            let heading = if arg.is_positional() {
+                tr("admin.arguments_heading")
+            } else {
+                tr("admin.options_heading")
+            };
+            arg.help_heading(heading)
+        })
+    };
+
+    let command = Args::command()
+        .about(tr("admin.about"))
+        .arg(
+            Arg::new("help")
+                .short('h')
+                .long("help")
+                .action(ArgAction::Help)
+                .help(tr("admin.help_help"))
+                .global(true),
+        )
+        .subcommand_help_heading(tr("admin.commands_heading"))
+        .mut_arg("record", |a| a.help(tr("admin.record_help")))
+        .mut_arg("scrollback", |a| a.help(tr("admin.scrollback_help")))
+        .mut_subcommand("migrate", |c| {
+            c.about(tr("admin.migrate_about"))
+                .subcommand_help_heading(tr("admin.commands_heading"))
+                .mut_subcommand("up", |c| {
+                    c.about(tr("admin.migrate_up_about"))
+                        .mut_arg("to", |a| a.help(tr("admin.migrate_up_to_help")))
+                })
+                .mut_subcommand("down", |c| {
+                    c.about(tr("admin.migrate_down_about"))
+                        .mut_arg("steps", |a| a.help(tr("admin.migrate_down_steps_help")))
+                })
+                .mut_subcommand("status", |c| c.about(tr("admin.migrate_status_about")))
+                .mut_subcommands(localize_args)
+        })
+        .mut_subcommand("build", |c| {
+            c.about(tr("admin.build_about"))
+                .mut_arg("task", |a| a.help(tr("admin.build_task_help")))
+                .mut_arg("context", |a| a.help(tr("admin.build_context_help")))
+                .mut_arg("task_sources", |a| {
+                    a.help(tr("admin.build_task_sources_help"))
+                })
+                .mut_arg("force", |a| a.help(tr("admin.build_force_help")))
+        });
+    localize_args(command).mut_subcommands(localize_args)
+}
+
+/// Parses the command line with the help in the language of the interface,
+/// so the locale must already be initialized
+pub fn parse_args() -> Args {
+    let matches = localized_command().get_matches();
+    Args::from_arg_matches(&matches).unwrap_or_else(|e| e.exit())
+}
+
+pub async fn run(command: AdminCommand) -> Result<(), AppError> {
+    match command {
+        AdminCommand::Migrate { action } => run_migrate(action)?,
+        AdminCommand::Build {
+            task,
+            context,
+            task_sources,
+            force,
+        } => {
+            let defaults = BuildOptions::from_config();
+            let options = BuildOptions {
+                context: context.unwrap_or(defaults.context),
+                task_sources: task_sources.or(defaults.task_sources),
+                force,
+            };
+            run_build(task.as_deref(), options).await?
+        }
+    }
+    Ok(())
+}
+
+async fn run_build(task: Option<&str>, options: BuildOptions) -> Result<(), AppError> {
+    let targets = images::build_targets(&options.context, task)?;
+    let (sender, mut receiver) = mpsc::unbounded_channel();
+    let print_events = async {
+        while let Some(event) = receiver.recv().await {
+            match event {
+                BuildEvent::Started(image) => println!("==> {}", image),
+                BuildEvent::Output(line) => println!("{}", line),
+                BuildEvent::UpToDate(image) => {
+                    println!("{}", tr_fmt("images.up_to_date", &[("image", &image)]))
+                }
+                BuildEvent::Built(image, hash) => println!(
+                    "{}",
+                    tr_fmt("images.built", &[("image", &image), ("hash", &hash)])
+                ),
+            }
+        }
+    };
+
+    let (result, ()) = tokio::join!(images::build_images(targets, options, sender), print_events);
+    result?;
+    Ok(())
+}
+
+fn run_migrate(action: MigrateAction) -> Result<(), MigrationError> {
+    let mut repo = Repo::open()?;
+
+    match action {
+        MigrateAction::Up { to } => {
+            let applied = repo.migrate_up(to)?;
+            if applied.is_empty() {
+                println!("{}", tr("admin.nothing_to_apply"));
+            }
+            for name in applied {
+                println!("{}", tr_fmt("admin.applied", &[("name", &name)]));
+            }
+        }
+        MigrateAction::Down { steps } => {
+            let reverted = repo.migrate_down(steps)?;
+            if reverted.is_empty() {
+                println!("{}", tr("admin.nothing_to_revert"));
+            }
+            for name in reverted {
+                println!("{}", tr_fmt("admin.reverted", &[("name", &name)]));
+            }
+        }
+        MigrateAction::Status => {
+            for status in repo.migration_status()? {
+                let state = match status.state {
+                    MigrationState::Applied(at) => tr_fmt("admin.state_applied", &[("at", &at)]),
+                    MigrationState::Pending => tr("admin.state_pending").to_string(),
+                    MigrationState::Modified => tr("admin.state_modified").to_string(),
+                    MigrationState::Missing(at) => tr_fmt("admin.state_missing", &[("at", &at)]),
+                };
+                println!("{:<14}  {:<45}  {}", status.version, status.name, state);
+            }
+        }
+    }
+
+    Ok(())
+}
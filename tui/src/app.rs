@@ -1,24 +1,53 @@
+use crate::config::config;
 use crate::db::{
     Attempt, AttemptCreate, Repo, Task, TaskStatus, Test, TestCreate, TestResult, User,
 };
-use crate::docker;
-use crate::io;
+use crate::docker::{self, ExecOutcome, GradingSnapshot};
+use crate::error::AppError;
+use crate::grading;
+use crate::history;
+use crate::images::BuildProgress;
+use crate::locale::{self, tr_fmt};
+use crate::manifest::{self, GradingManifest, TaskManifest, TestManifest};
 use crate::popup::Popup;
+use crate::preflight::PreflightReport;
+use crate::pty::copy_mode::CopyMode;
 use crate::pty::ui::PtyExitStatus;
+use crate::recording::Recorder;
 use ratatui::DefaultTerminal;
 use ratatui::Frame;
 use ratatui::widgets::{ListState, ScrollbarState, TableState};
-use rusqlite::Error as SqlError;
+use std::collections::HashSet;
 use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
 
 pub const VERSION: &str = "0.1.0";
 
+/// Test results of a graded task together with its whole .bash_history at that moment
+pub struct GradedAttempt {
+    task_id: i64,
+    tests: Vec<TestCreate>,
+    full_history: String,
+}
+
 #[derive(PartialEq)]
 pub enum AppStatus {
     Idling,
     RunningTask,
     RestartingTask,
+    /// Recreates the outdated container of the task under cursor and runs the task
+    RecreatingContainer {
+        keep_repository: bool,
+    },
     ShowingAttempts,
+    PlayingRecording,
+    RunningPreflight,
+    ShowingPreflight,
+    BuildingImages,
+    ShowingImageBuild,
     Exiting,
 }
 
@@ -58,16 +87,32 @@ impl TestsTableConfig {
     }
 }
 
+pub struct HistoryViewConfig {
+    pub scroll: usize,
+    pub scrollbar_state: ScrollbarState,
+}
+
+impl HistoryViewConfig {
+    pub fn default() -> Self {
+        HistoryViewConfig {
+            scroll: 0,
+            scrollbar_state: ScrollbarState::default(),
+        }
+    }
+}
+
 #[derive(PartialEq)]
 pub enum AttemptManagerStatus {
     SelectingAttempts,
     SelectingTests,
+    ViewingHistory,
 }
 
 pub struct AttemptManagerConfig {
     pub status: AttemptManagerStatus,
     pub attempts_table_config: AttemptsTableConfig,
     pub tests_table_config: TestsTableConfig,
+    pub history_view_config: HistoryViewConfig,
 }
 
 impl AttemptManagerConfig {
@@ -76,52 +121,97 @@ impl AttemptManagerConfig {
             status: AttemptManagerStatus::SelectingAttempts,
             attempts_table_config: AttemptsTableConfig::default(),
             tests_table_config: TestsTableConfig::default(),
+            history_view_config: HistoryViewConfig::default(),
         }
     }
 }
 
+/// Panels shown next to the terminal of a running task
+#[derive(Clone, Copy)]
+pub struct SidePanels {
+    /// Task statement and results of the last attempt, on the left
+    pub task_description: bool,
+    /// Live graph of the task repository, on the right
+    pub git_graph: bool,
+}
+
 pub struct Context {
-    pub user: Result<User, SqlError>,
-    pub tasks: Result<Vec<Task>, SqlError>,
+    pub user: User,
+    /// Never empty, App::new fails without tasks
+    pub tasks: Vec<Task>,
 }
 
 // TODO: Rewrite tasks in struct
 pub struct App {
     pub repo: Repo,
+    pub manifests: Vec<TaskManifest>,
     pub context: Context,
     pub table_state: TableState,
     pub task_under_cursor: usize,
     pub status: AppStatus,
     pub active_popup: Option<Popup>,
+    pub record_sessions: bool,
+    /// Lines of terminal output kept above the screen of a running task
+    pub scrollback: usize,
+    pub copy_mode: Option<CopyMode>,
+    /// The prefix key was pressed, the next key is a command of the trainer
+    pub prefix_pressed: bool,
+    pub side_panels: SidePanels,
+    /// Recorder of the running task session, attempts submitted during it are linked to it
+    pub recorder: Option<Arc<Mutex<Recorder>>>,
+    /// Results of the latest preflight checks, None while they are running
+    pub preflight: Option<PreflightReport>,
+    /// Output of the last image build started from the preflight screen
+    pub image_build: Option<BuildProgress>,
+    /// Outdated containers the user chose to keep working in during this session
+    pub kept_outdated_containers: HashSet<String>,
 
     pub attempt_manager_config: AttemptManagerConfig,
 }
 
 impl App {
-    pub fn new() -> App {
+    pub fn new() -> Result<App, AppError> {
         let mut table_state = TableState::default();
         table_state.select(Some(0));
-        let mut repo = Repo::init_database();
-        let username = whoami::username()
-            .expect("While getting username:")
-            .to_string()
-            .replace(" ", "-");
-        if !repo.user_exists(&username).expect("While working with db:") {
-            let _ = repo.create_user(&username);
+        let mut repo = Repo::init_database()?;
+        let mut manifests = manifest::load_manifests(manifest::tasks_dir())?;
+        // База общая для всех пользователей, поэтому в неё пишутся тексты без перевода
+        repo.sync_tasks(&manifests)?;
+        for manifest in &mut manifests {
+            manifest.localize(locale::current());
         }
-        let user = repo.get_user_by_username(username);
-        App {
-            context: Context {
-                tasks: repo.get_tasks_user_local(user.as_ref().unwrap().id),
-                user,
-            },
+        let username = whoami::username()?.replace(" ", "-");
+        if !repo.user_exists(&username)? {
+            repo.create_user(&username)?;
+        }
+        let user = repo.get_user_by_username(username)?;
+        let mut tasks = repo.get_tasks_user_local(user.id)?;
+        localize_tasks(&mut tasks, &manifests);
+        if tasks.is_empty() {
+            return Err(AppError::NoTasksError);
+        }
+        Ok(App {
+            context: Context { user, tasks },
             repo,
+            manifests,
             table_state,
             task_under_cursor: 0,
             status: AppStatus::Idling,
             active_popup: None,
+            record_sessions: config().ui.record_sessions,
+            scrollback: config().ui.scrollback,
+            copy_mode: None,
+            prefix_pressed: false,
+            side_panels: SidePanels {
+                task_description: config().ui.task_description,
+                git_graph: config().ui.git_graph,
+            },
+            recorder: None,
+            preflight: None,
+            image_build: None,
+            kept_outdated_containers: HashSet::new(),
             attempt_manager_config: AttemptManagerConfig::default(),
-        }
+        })
     }
 
     // На то, чтобы придумать эту функцию ушло 500 миллиардов нейронов
@@ -129,28 +219,47 @@ impl App {
         match self.status {
             AppStatus::Idling => self.render_main_menu(frame),
             AppStatus::ShowingAttempts => self.render_attempt_manager(frame),
+            AppStatus::RunningPreflight | AppStatus::ShowingPreflight => {
+                self.render_preflight(frame)
+            }
+            AppStatus::BuildingImages | AppStatus::ShowingImageBuild => {
+                self.render_image_build(frame)
+            }
             _ => {}
         }
     }
 
-    pub fn handle_events(&mut self) -> io::Result<()> {
+    pub fn handle_events(&mut self) -> std::io::Result<()> {
         match self.status {
             AppStatus::Idling => self.main_menu_handle_events()?,
             AppStatus::ShowingAttempts => self.attempt_manager_handle_events()?,
+            AppStatus::ShowingPreflight => self.preflight_handle_events()?,
+            AppStatus::ShowingImageBuild => self.image_build_handle_events()?,
             _ => {}
         };
         Ok(())
     }
 
-    pub async fn run_app(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+    pub async fn run_app(&mut self, terminal: &mut DefaultTerminal) -> std::io::Result<()> {
+        // Экран проверки показывается при запуске, только если что-то не готово
+        self.run_preflight(terminal).await?;
+        if self
+            .preflight
+            .as_ref()
+            .is_some_and(|report| !report.is_ok())
+        {
+            self.status = AppStatus::ShowingPreflight;
+        }
+
         while self.status != AppStatus::Exiting {
-            // self.status = AppStatus::ShowingAttempts;
-            self.update_context();
+            if let Err(err) = self.update_context() {
+                self.active_popup = Some(Popup::Error(err.to_string()));
+            }
             terminal.draw(|f| self.render(f))?;
             self.handle_events()?;
             match self.status {
                 AppStatus::RestartingTask => {
-                    if let Err(err) = docker::restart_task(self.task_under_cursor()).await {
+                    if let Err(err) = self.restart_task().await {
                         self.active_popup = Some(Popup::Error(err.to_string()))
                     };
                     self.status = AppStatus::Idling;
@@ -164,32 +273,95 @@ impl App {
                     match self.prepare_pty_bollard(terminal).await {
                         Err(err) => self.active_popup = Some(Popup::Error(err.to_string())),
                         Ok(PtyExitStatus::RestartTask) => {
-                            if let Err(err) = docker::restart_task(self.task_under_cursor()).await {
+                            if let Err(err) = self.restart_task().await {
                                 self.active_popup = Some(Popup::Error(err.to_string()))
                             };
                         }
                         Ok(PtyExitStatus::Exit) => {
                             self.status = AppStatus::Idling;
                         }
+                        Ok(PtyExitStatus::ContainerOutdated) => {
+                            self.active_popup = Some(Popup::ContainerOutdated);
+                            self.status = AppStatus::Idling;
+                        }
+                    }
+                }
+                AppStatus::RecreatingContainer { keep_repository } => {
+                    match self.recreate_container(keep_repository).await {
+                        Ok(()) => self.status = AppStatus::RunningTask,
+                        Err(err) => {
+                            self.active_popup = Some(Popup::Error(err.to_string()));
+                            self.status = AppStatus::Idling;
+                        }
                     }
                 }
+                AppStatus::RunningPreflight => {
+                    self.run_preflight(terminal).await?;
+                    self.status = AppStatus::ShowingPreflight;
+                }
+                AppStatus::BuildingImages => {
+                    self.build_images(terminal).await?;
+                    self.status = AppStatus::ShowingImageBuild;
+                }
+                AppStatus::PlayingRecording => {
+                    if let Err(err) = self.play_recording(terminal).await {
+                        self.active_popup = Some(Popup::Error(err.to_string()))
+                    }
+                    self.status = AppStatus::ShowingAttempts;
+                }
                 _ => {}
             }
         }
         Ok(())
     }
 
+    /// Recreates the container of the task under cursor. The new container starts
+    /// with an empty .bash_history, so the stored history offset is reset too
+    async fn restart_task(&mut self) -> Result<(), AppError> {
+        docker::restart_task(self.task_under_cursor()).await?;
+
+        let task_id = self.task_under_cursor().id;
+        self.repo
+            .set_history_offset(self.context.user.id, task_id, 0)?;
+        Ok(())
+    }
+
+    /// Recreates the container of the task under cursor from the current image,
+    /// with `keep_repository` the repository of the student is carried over
+    async fn recreate_container(&mut self, keep_repository: bool) -> Result<(), AppError> {
+        let task = self.task_under_cursor();
+        let repository = self
+            .manifest_of(task)
+            .map(|manifest| manifest.repository_path())
+            .unwrap_or_else(|| manifest::STUDENT_HOME.to_string());
+        let keep_path = keep_repository.then_some(repository.as_str());
+        docker::recreate_task_container(task, keep_path).await?;
+
+        // .bash_history переносится, только если переносится вся домашняя папка
+        if keep_path != Some(manifest::STUDENT_HOME) {
+            let task_id = task.id;
+            self.repo
+                .set_history_offset(self.context.user.id, task_id, 0)?;
+        }
+        Ok(())
+    }
+
     pub fn task_under_cursor(&self) -> &Task {
-        &self.context.tasks.as_ref().expect("while working with db:")[self.task_under_cursor]
+        &self.context.tasks[self.task_under_cursor]
     }
 
     pub fn task_under_cursor_mut(&mut self) -> &mut Task {
-        &mut self.context.tasks.as_mut().expect("while working with db:")[self.task_under_cursor]
+        &mut self.context.tasks[self.task_under_cursor]
+    }
+
+    pub fn manifest_of(&self, task: &Task) -> Option<&TaskManifest> {
+        self.manifests
+            .iter()
+            .find(|m| m.work_name == task.work_name)
     }
 
     pub fn attempts_of_choosed_task(&self) -> &Vec<Attempt> {
-        let task = self.task_under_cursor();
-        task.attempts.as_ref().expect("While working wih db:")
+        &self.task_under_cursor().attempts
     }
 
     pub fn attempt_under_cursor(&self) -> Option<&Attempt> {
@@ -208,100 +380,145 @@ impl App {
     pub fn tests_of_choosed_attempt(&self) -> Vec<Test> {
         let attempt = self.attempt_under_cursor();
         match attempt {
-            Some(attempt) => attempt
-                .tests
-                .as_ref()
-                .expect("While working with db:")
-                .to_vec(),
+            Some(attempt) => attempt.tests.to_vec(),
             None => Vec::new(),
         }
     }
 
-    pub async fn test_submitted_task(&mut self) {
+    /// Starts grading the task under cursor in the background, so that its terminal
+    /// stays responsive. The result is saved with `save_attempt`
+    pub fn start_grading(&self) -> Result<JoinHandle<Result<GradedAttempt, AppError>>, AppError> {
         let task = self.task_under_cursor();
 
-        #[cfg(debug_assertions)]
-        let path = format!("tests/{}", task.work_name);
-
-        #[cfg(not(debug_assertions))]
-        let path = format!("/var/lib/git-trainer/tests/{}", task.work_name);
-
-        let count = fs::read_dir(&path)
-            .expect("No test directory for task")
-            .count();
-
-        docker::copy_directory(&task.container_name, &path, "/etc/git-trainer/tests")
-            .await
-            .unwrap();
-
-        let mut test_results = Vec::new();
-        let mut failed = false;
-        for i in 1..count + 1 {
-            if !failed {
-                let cmd = format!("/etc/git-trainer/tests/test{}.sh", i);
-                let res = docker::exec_command(task, &cmd).await.unwrap();
-                if res.exit_code == 0 {
-                    test_results.push(TestCreate {
-                        description: res.output,
-                        result: 0,
-                    });
-                } else {
-                    test_results.push(TestCreate {
-                        description: res.output,
-                        result: 1,
-                    });
-                    failed = true;
-                }
-            } else {
-                let res = format!("{}. Не выполнялся.", i);
-                test_results.push(TestCreate {
-                    description: res,
-                    result: 2,
-                });
+        let path = config().paths.tests.join(&task.work_name);
+        let missing_tests =
+            |e| AppError::MissingTestsError(task.work_name.clone(), path.clone(), e);
+
+        let (tests, grading_settings, order) = match self.manifest_of(task) {
+            Some(manifest) if !manifest.tests.is_empty() => (
+                manifest.tests.clone(),
+                manifest.grading.clone(),
+                manifest.test_execution_order()?,
+            ),
+            manifest => {
+                // Без списка тестов в манифесте каждый testN.sh зависит от предыдущего
+                let count = fs::read_dir(&path).map_err(missing_tests)?.count();
+                let tests = (1..count + 1)
+                    .map(|i| TestManifest {
+                        script: format!("test{}.sh", i),
+                        timeout_secs: None,
+                        depends_on: if i > 1 {
+                            vec![format!("test{}.sh", i - 1)]
+                        } else {
+                            Vec::new()
+                        },
+                    })
+                    .collect();
+                let grading_settings = manifest.map(|m| m.grading.clone()).unwrap_or_default();
+                (tests, grading_settings, (0..count).collect())
             }
+        };
+        if !path.is_dir() {
+            return Err(missing_tests(std::io::ErrorKind::NotFound.into()));
         }
 
-        let _ = docker::exec_command(task, "sudo rm -rf /etc/git-trainer/tests/*").await;
+        let task_id = task.id;
+        let container_name = task.container_name.clone();
+        let work_name = task.work_name.clone();
+        Ok(tokio::spawn(async move {
+            let snapshot = docker::create_grading_snapshot(&container_name, &work_name).await?;
+            // История берётся из снимка: команды, набранные во время проверки, относятся
+            // уже к следующей попытке
+            let results: Result<_, AppError> = async {
+                let full_history = docker::exec_command(
+                    &snapshot.container_name,
+                    "cat /home/student/.bash_history",
+                )
+                .await?
+                .output;
+                let tests = run_tests(&snapshot, &path, &tests, &grading_settings, order).await?;
+                Ok((tests, full_history))
+            }
+            .await;
+            // Снимок удаляется и тогда, когда проверка прервалась с ошибкой
+            let _ = docker::remove_grading_snapshot(&snapshot).await;
+            let (tests, full_history) = results?;
+
+            Ok(GradedAttempt {
+                task_id,
+                tests,
+                full_history,
+            })
+        }))
+    }
 
-        let user_id = self
-            .context
-            .user
-            .as_ref()
-            .expect("While working with db:")
-            .id;
+    /// Saves the graded attempt and returns its test results
+    pub fn save_attempt(&mut self, graded: GradedAttempt) -> Result<Vec<TestCreate>, AppError> {
+        let user_id = self.context.user.id;
+        let task_id = graded.task_id;
+
+        // В попытку попадают только команды, введённые после предыдущей попытки
+        let offset = self.repo.get_history_offset(user_id, task_id)?;
+        let (bash_history, offset) = history::history_since(&graded.full_history, offset as usize);
+        self.repo
+            .set_history_offset(user_id, task_id, offset as i64)?;
 
-        let bash_history = docker::exec_command(task, "cat /home/student/.bash_history")
-            .await
-            .unwrap()
-            .output;
+        let hints_used = self.repo.get_unlocked_hints_count(user_id, task_id)?;
 
         let attempt = AttemptCreate {
-            tests: test_results,
-            task_id: task.id,
-            user_id: user_id,
-            bash_history: bash_history.clone(),
+            tests: graded.tests.clone(),
+            task_id,
+            user_id,
+            bash_history,
+            hints_used,
+            recording: self.recorder.as_ref().map(|recorder| {
+                recorder
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .path()
+                    .to_string_lossy()
+                    .to_string()
+            }),
         };
 
-        self.repo
-            .create_attempt(attempt)
-            .expect("While working with db:");
+        self.repo.create_attempt(attempt)?;
+
+        Ok(graded.tests)
     }
 
-    pub fn update_context(&mut self) {
-        let user_id = self.context.user.as_ref().unwrap().id;
-        let tasks = self.context.tasks.as_mut().expect("While working with db:");
-        _ = self.repo.load_new_tasks(user_id, tasks);
+    /// Unlocks the next hint of the task under cursor. Returns texts of all unlocked hints,
+    /// the total number of hints and whether a new hint was unlocked
+    pub fn unlock_next_hint(&mut self) -> Result<(Vec<String>, usize, bool), AppError> {
+        let task = self.task_under_cursor();
+        let hints: Vec<String> = self
+            .manifest_of(task)
+            .map(|manifest| manifest.hints.iter().map(|h| h.text.clone()).collect())
+            .unwrap_or_default();
+
+        let user_id = self.context.user.id;
+        let unlocked = self.repo.get_unlocked_hints_count(user_id, task.id)? as usize;
+
+        let revealed = unlocked < hints.len();
+        if revealed {
+            self.repo.unlock_hint(user_id, task.id, unlocked as i64)?;
+        }
+
+        let unlocked = (unlocked + revealed as usize).min(hints.len());
+        Ok((hints[..unlocked].to_vec(), hints.len(), revealed))
+    }
+
+    pub fn update_context(&mut self) -> Result<(), AppError> {
+        let user_id = self.context.user.id;
+        let tasks = &mut self.context.tasks;
+        self.repo.load_new_tasks(user_id, tasks)?;
         for task in tasks.iter_mut() {
-            let attempts = task.attempts.as_ref().expect("While working with db:");
-            if attempts.is_empty() {
+            if task.attempts.is_empty() {
                 continue;
             }
 
-            let all_passed = attempts.iter().any(|attempt| {
+            let all_passed = task.attempts.iter().any(|attempt| {
                 attempt
                     .tests
-                    .as_ref()
-                    .expect("While working with db:")
                     .iter()
                     .all(|test| test.result == TestResult::Passed)
             });
@@ -314,11 +531,88 @@ impl App {
         }
 
         for task in tasks.iter() {
-            let _ = self
-                .repo
-                .update_task_status(task.id, user_id, task.status.clone());
+            self.repo
+                .update_task_status(task.id, user_id, task.status.clone())?;
         }
 
-        self.context.tasks = self.repo.get_tasks_user_local(user_id);
+        let mut tasks = self.repo.get_tasks_user_local(user_id)?;
+        localize_tasks(&mut tasks, &self.manifests);
+        // Задания не удаляются из базы, но курсор не должен выйти за список
+        self.task_under_cursor = self.task_under_cursor.min(tasks.len().saturating_sub(1));
+        if !tasks.is_empty() {
+            self.context.tasks = tasks;
+        }
+        Ok(())
+    }
+}
+
+/// Replaces the texts of tasks loaded from the database with the ones of their localized manifests
+fn localize_tasks(tasks: &mut [Task], manifests: &[TaskManifest]) {
+    for task in tasks {
+        let Some(manifest) = manifests.iter().find(|m| m.work_name == task.work_name) else {
+            continue;
+        };
+        task.name = manifest.name.clone();
+        task.description = manifest.description.clone();
+        task.extended_description = manifest.extended_description.trim().to_string();
+    }
+}
+
+/// Copies the tests into the snapshot container and runs them in `order`,
+/// respecting their dependencies and the time limits
+async fn run_tests(
+    snapshot: &GradingSnapshot,
+    path: &Path,
+    tests: &[TestManifest],
+    grading_settings: &GradingManifest,
+    order: Vec<usize>,
+) -> Result<Vec<TestCreate>, AppError> {
+    docker::copy_directory(&snapshot.container_name, path, "/etc/git-trainer/tests").await?;
+
+    let deadline = Instant::now() + Duration::from_secs(grading_settings.attempt_timeout_secs);
+    let mut results: Vec<Option<TestCreate>> = tests.iter().map(|_| None).collect();
+    for i in order {
+        let test = &tests[i];
+        let failed_dependencies: Vec<String> = test
+            .depends_on
+            .iter()
+            .filter_map(|dependency| {
+                let d = tests.iter().position(|t| &t.script == dependency)?;
+                let passed = results[d]
+                    .as_ref()
+                    .is_some_and(|r| r.result == TestResult::Passed);
+                (!passed).then(|| (d + 1).to_string())
+            })
+            .collect();
+
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        let result = if !failed_dependencies.is_empty() {
+            grading::not_executed(tr_fmt(
+                "grading.skipped_dependencies",
+                &[
+                    ("number", &(i + 1)),
+                    ("tests", &failed_dependencies.join(", ")),
+                ],
+            ))
+        } else if remaining.is_zero() {
+            grading::not_executed(tr_fmt("grading.skipped_timeout", &[("number", &(i + 1))]))
+        } else {
+            let timeout = Duration::from_secs(
+                test.timeout_secs
+                    .unwrap_or(grading_settings.test_timeout_secs),
+            )
+            .min(remaining);
+            let cmd = format!("/etc/git-trainer/tests/{}", test.script);
+            let res =
+                docker::exec_command_with_timeout(&snapshot.container_name, &cmd, timeout).await?;
+            match res {
+                ExecOutcome::Finished(res) => {
+                    grading::parse_test_output(&res.output, res.exit_code)
+                }
+                ExecOutcome::TimedOut(output) => grading::timed_out(i + 1, &output, timeout),
+            }
+        };
+        results[i] = Some(result);
     }
+    Ok(results.into_iter().flatten().collect())
 }
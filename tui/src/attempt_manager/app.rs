@@ -1,9 +1,18 @@
-use crate::app::{App, AttemptsTableConfig};
+use crate::app::{App, AttemptsTableConfig, HistoryViewConfig};
 use crate::{AppStatus, app::AttemptManagerStatus};
 use crossterm::event::{self, Event, KeyCode};
 use std::io;
 
+/// How many lines PageUp and PageDown scroll the bash history
+const HISTORY_PAGE: isize = 10;
+
 impl App {
+    // Прокрутка истории команд, выход за конец ограничивается при отрисовке
+    fn scroll_history(&mut self, delta: isize) {
+        let history = &mut self.attempt_manager_config.history_view_config;
+        history.scroll = history.scroll.saturating_add_signed(delta);
+    }
+
     // Прокрутка вниз
     fn next_attempt(&mut self) {
         let attempts = self.attempts_of_choosed_task();
@@ -29,6 +38,7 @@ impl App {
         self.attempt_manager_config
             .attempts_table_config
             .attempt_under_cursor = i;
+        self.attempt_manager_config.history_view_config = HistoryViewConfig::default();
     }
 
     // Прокрутка вверх
@@ -56,10 +66,18 @@ impl App {
         self.attempt_manager_config
             .attempts_table_config
             .attempt_under_cursor = i;
+        self.attempt_manager_config.history_view_config = HistoryViewConfig::default();
     }
 
     pub fn attempt_manager_handle_events(&mut self) -> io::Result<()> {
         if let Event::Key(key) = event::read()? {
+            if self.active_popup.is_some() {
+                if matches!(key.code, KeyCode::Enter | KeyCode::Esc) {
+                    self.active_popup = None;
+                }
+                return Ok(());
+            }
+
             match key.code {
                 KeyCode::Char('q') => {
                     self.attempt_manager_config.attempts_table_config =
@@ -69,24 +87,46 @@ impl App {
                 KeyCode::Down | KeyCode::Char('j') => match self.attempt_manager_config.status {
                     AttemptManagerStatus::SelectingAttempts => self.next_attempt(),
                     AttemptManagerStatus::SelectingTests => self.next_test(),
+                    AttemptManagerStatus::ViewingHistory => self.scroll_history(1),
                 },
                 KeyCode::Up | KeyCode::Char('k') => match self.attempt_manager_config.status {
                     AttemptManagerStatus::SelectingAttempts => self.previous_attempt(),
                     AttemptManagerStatus::SelectingTests => self.previous_test(),
+                    AttemptManagerStatus::ViewingHistory => self.scroll_history(-1),
                 },
-                KeyCode::Right | KeyCode::Char('l') => {
-                    self.attempt_manager_config.status = AttemptManagerStatus::SelectingTests
+                KeyCode::Char('p')
+                    if self
+                        .attempt_under_cursor()
+                        .is_some_and(|attempt| attempt.recording.is_some()) =>
+                {
+                    self.status = AppStatus::PlayingRecording;
                 }
-                KeyCode::Left | KeyCode::Char('h') => {
-                    self.attempt_manager_config.status = AttemptManagerStatus::SelectingAttempts;
-                    self.attempt_manager_config
-                        .tests_table_config
-                        .list_state
-                        .select(Some(0));
-                    self.attempt_manager_config
-                        .tests_table_config
-                        .test_under_cursor = 0;
+                KeyCode::PageDown => self.scroll_history(HISTORY_PAGE),
+                KeyCode::PageUp => self.scroll_history(-HISTORY_PAGE),
+                KeyCode::Right | KeyCode::Char('l') => {
+                    self.attempt_manager_config.status = match self.attempt_manager_config.status {
+                        AttemptManagerStatus::SelectingAttempts => {
+                            AttemptManagerStatus::SelectingTests
+                        }
+                        _ => AttemptManagerStatus::ViewingHistory,
+                    }
                 }
+                KeyCode::Left | KeyCode::Char('h') => match self.attempt_manager_config.status {
+                    AttemptManagerStatus::ViewingHistory => {
+                        self.attempt_manager_config.status = AttemptManagerStatus::SelectingTests;
+                    }
+                    _ => {
+                        self.attempt_manager_config.status =
+                            AttemptManagerStatus::SelectingAttempts;
+                        self.attempt_manager_config
+                            .tests_table_config
+                            .list_state
+                            .select(Some(0));
+                        self.attempt_manager_config
+                            .tests_table_config
+                            .test_under_cursor = 0;
+                    }
+                },
 
                 _ => {}
             }
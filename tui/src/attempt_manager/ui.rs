@@ -1,5 +1,7 @@
 use crate::app::{App, AttemptManagerStatus, VERSION};
 use crate::db::{TaskStatus, TestResult};
+use crate::history;
+use crate::locale::{tr, tr_fmt};
 use ratatui::Frame;
 use ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
 use ratatui::style::{Color, Style, Stylize};
@@ -24,7 +26,11 @@ impl App {
         let title_area = global_area[0];
         let main_area = Layout::default()
             .direction(Direction::Horizontal)
-            .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+            .constraints([
+                Constraint::Percentage(30),
+                Constraint::Percentage(40),
+                Constraint::Percentage(30),
+            ])
             .split(global_area[2]);
 
         let attempts_area = main_area[0];
@@ -63,12 +69,24 @@ impl App {
         frame.render_widget(paragraph, area);
         render_attempts_table(frame, self, attempts_area);
         render_tests_table(frame, self, main_area[1]);
+        render_bash_history(frame, self, main_area[2]);
 
-        let explanation = "← ↑ ↓ → — перемещение, q — выход".to_string();
+        let has_recording = self
+            .attempt_under_cursor()
+            .is_some_and(|attempt| attempt.recording.is_some());
+        let explanation = if has_recording {
+            tr("attempt_manager.footer_with_recording")
+        } else {
+            tr("attempt_manager.footer")
+        };
         let explanation = Paragraph::new(explanation)
             .style(Style::default())
             .alignment(Alignment::Center);
         frame.render_widget(explanation, global_area[3]);
+
+        if let Some(popup) = &self.active_popup {
+            popup.render(frame);
+        }
     }
 }
 
@@ -77,18 +95,24 @@ pub fn render_attempts_table(frame: &mut Frame, app: &mut App, area: Rect) {
 
     let mut rows = Vec::new();
     for (i, attempt) in attempts.iter().enumerate() {
-        let tests = app
-            .repo
-            .get_attempt_tests(attempt.id)
-            .expect("While working with db:");
+        let tests = &attempt.tests;
         let passed_count = tests
             .iter()
             .filter(|t| t.result == TestResult::Passed)
             .count();
         let total_count = tests.len();
-        let tests_passed = format!("{}/{}", passed_count, total_count);
+        let timed_out = tests.iter().any(|t| t.result == TestResult::TimedOut);
+        let tests_passed = if timed_out {
+            tr_fmt(
+                "attempt_manager.tests_timed_out",
+                &[("passed", &passed_count), ("total", &total_count)],
+            )
+        } else {
+            format!("{}/{}", passed_count, total_count)
+        };
 
-        let style = if app.attempt_manager_config.status == AttemptManagerStatus::SelectingTests {
+        let style = if app.attempt_manager_config.status != AttemptManagerStatus::SelectingAttempts
+        {
             if passed_count == total_count {
                 Style::new().fg(Color::LightGreen)
             } else {
@@ -107,25 +131,31 @@ pub fn render_attempts_table(frame: &mut Frame, app: &mut App, area: Rect) {
             Style::new().fg(Color::Red)
         };
         let row = Row::new(vec![
-            attempt
-                .timestamp
-                .as_ref()
-                .expect("While working with db:")
-                .clone(),
+            attempt.timestamp.as_deref().unwrap_or("?").to_string(),
             tests_passed,
+            attempt.hints_used.to_string(),
         ])
         .style(style);
 
         rows.push(row);
     }
 
-    let header = Row::new(vec!["Дата попытки", "Тесты"]).bottom_margin(1);
+    let header = Row::new(vec![
+        tr("attempt_manager.column_date"),
+        tr("attempt_manager.column_tests"),
+        tr("attempt_manager.column_hints"),
+    ])
+    .bottom_margin(1);
 
-    let widths = [Constraint::Percentage(70), Constraint::Percentage(30)];
+    let widths = [
+        Constraint::Length(20),
+        Constraint::Fill(1),
+        Constraint::Length(9),
+    ];
 
     let style = match app.attempt_manager_config.status {
         AttemptManagerStatus::SelectingAttempts => Style::default().bg(Color::DarkGray),
-        AttemptManagerStatus::SelectingTests => Style::default(),
+        _ => Style::default(),
     };
     let table = Table::new(rows, widths)
         .header(header)
@@ -246,19 +276,24 @@ pub fn render_tests_table(frame: &mut Frame, app: &mut App, area: Rect) {
 
     let title = if total_count != 0 {
         if passed_count == total_count {
-            format!("Тесты: пройдены все ({}/{})", passed_count, total_count)
+            tr_fmt(
+                "attempt_manager.all_tests_passed",
+                &[("passed", &passed_count), ("total", &total_count)],
+            )
         } else {
-            format!("Тесты: пройдены не все ({}/{})", passed_count, total_count)
+            tr_fmt(
+                "attempt_manager.not_all_tests_passed",
+                &[("passed", &passed_count), ("total", &total_count)],
+            )
         }
     } else {
-        "Попыток ещё нет.".to_string()
+        tr("attempt_manager.no_attempts").to_string()
     };
     let items: Vec<ListItem> = tests
         .iter()
         .enumerate()
         .map(|(i, test)| {
-            let style = if app.attempt_manager_config.status
-                == AttemptManagerStatus::SelectingAttempts
+            let style = if app.attempt_manager_config.status != AttemptManagerStatus::SelectingTests
                 || i != app
                     .attempt_manager_config
                     .tests_table_config
@@ -268,13 +303,29 @@ pub fn render_tests_table(frame: &mut Frame, app: &mut App, area: Rect) {
                     TestResult::Passed => Style::default().fg(Color::LightGreen),
                     TestResult::Failed => Style::default().fg(Color::Red),
                     TestResult::NotExecuted => Style::default().fg(Color::DarkGray),
+                    TestResult::TimedOut => Style::default().fg(Color::Yellow),
                 }
             } else {
                 Style::default()
             };
 
             let max_width = area.width.saturating_sub(4) as usize;
-            let lines = wrap_text(&test.description, max_width);
+            let mut lines = wrap_text(&test.description, max_width);
+
+            let details = [
+                (tr("attempt_manager.expected"), test.expected.clone()),
+                (tr("attempt_manager.actual"), test.actual.clone()),
+                (tr("attempt_manager.hint"), test.hint.clone()),
+                (
+                    tr("attempt_manager.score"),
+                    test.score.map(|score| score.to_string()),
+                ),
+            ];
+            for (label, value) in details {
+                if let Some(value) = value {
+                    lines.extend(wrap_text(&format!("{}: {}", label, value), max_width));
+                }
+            }
 
             let text_lines: Vec<Line> = lines
                 .into_iter()
@@ -287,7 +338,7 @@ pub fn render_tests_table(frame: &mut Frame, app: &mut App, area: Rect) {
 
     let style = match app.attempt_manager_config.status {
         AttemptManagerStatus::SelectingTests => Style::default().bg(Color::DarkGray),
-        AttemptManagerStatus::SelectingAttempts => Style::default(),
+        _ => Style::default(),
     };
 
     let list = List::new(items)
@@ -341,3 +392,101 @@ pub fn render_tests_table(frame: &mut Frame, app: &mut App, area: Rect) {
             .scrollbar_state,
     );
 }
+
+/// Colors the command name and the subcommand of git commands, other commands are dimmed
+fn highlight_command(command: &str) -> Line<'static> {
+    let mut words = command.split_whitespace();
+    let mut first = words.next().unwrap_or_default();
+    let mut spans = Vec::new();
+
+    if first == "sudo" {
+        spans.push(Span::from("sudo ").fg(Color::DarkGray));
+        first = words.next().unwrap_or_default();
+    }
+
+    if first != "git" {
+        return Line::from(command.to_string()).fg(Color::DarkGray);
+    }
+
+    spans.push(Span::from("git").fg(Color::LightYellow).bold());
+    if let Some(subcommand) = words.next() {
+        spans.push(Span::raw(" "));
+        spans.push(Span::from(subcommand.to_string()).fg(Color::LightCyan));
+    }
+    let rest: Vec<&str> = words.collect();
+    if !rest.is_empty() {
+        spans.push(Span::raw(format!(" {}", rest.join(" "))));
+    }
+    Line::from(spans)
+}
+
+pub fn render_bash_history(frame: &mut Frame, app: &mut App, area: Rect) {
+    let commands = match app.attempt_under_cursor() {
+        Some(attempt) => history::parse_history(&attempt.bash_history),
+        None => Vec::new(),
+    };
+
+    let number_width = commands.len().to_string().len();
+    let lines: Vec<Line> = commands
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            let mut prefix = format!("{:>width$} ", i + 1, width = number_width);
+            if let Some(timestamp) = entry.timestamp {
+                prefix.push_str(&timestamp.format("%H:%M:%S ").to_string());
+            }
+
+            let mut line = highlight_command(&entry.command);
+            line.spans.insert(0, Span::from(prefix).fg(Color::DarkGray));
+            line
+        })
+        .collect();
+
+    let visible_height = area.height.saturating_sub(2) as usize;
+    let max_scroll = lines.len().saturating_sub(visible_height);
+    let history_config = &mut app.attempt_manager_config.history_view_config;
+    history_config.scroll = history_config.scroll.min(max_scroll);
+
+    let title = if commands.is_empty() {
+        tr("attempt_manager.history_empty").to_string()
+    } else {
+        tr_fmt("attempt_manager.history", &[("count", &commands.len())])
+    };
+    let border_style = match app.attempt_manager_config.status {
+        AttemptManagerStatus::ViewingHistory => Style::default().fg(Color::LightBlue),
+        _ => Style::default().fg(Color::White),
+    };
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title)
+                .border_style(border_style),
+        )
+        .scroll((history_config.scroll as u16, 0));
+
+    let scrollbar = Scrollbar::default()
+        .orientation(ScrollbarOrientation::VerticalRight)
+        .begin_symbol(Some("▲"))
+        .end_symbol(Some("▼"));
+
+    let scrollbar_area = Rect {
+        x: area.x + area.width - 1,
+        y: area.y + 1,
+        width: 1,
+        height: area.height.saturating_sub(2),
+    };
+
+    history_config.scrollbar_state = history_config
+        .scrollbar_state
+        .content_length(max_scroll + 1)
+        .position(history_config.scroll);
+
+    frame.render_widget(paragraph, area);
+    frame.render_stateful_widget(
+        scrollbar,
+        scrollbar_area,
+        &mut history_config.scrollbar_state,
+    );
+}
@@ -0,0 +1,257 @@
+use crate::pty::keys::KeyBinding;
+use crossterm::event::{KeyCode, KeyModifiers};
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use thiserror::Error;
+
+/// Settings shared by every user of the machine
+const SYSTEM_CONFIG_PATH: &str = "/etc/git-trainer/config.toml";
+/// Environment variables `GIT_TRAINER_<SECTION>_<KEY>` override single settings,
+/// e.g. `GIT_TRAINER_DOCKER_ENDPOINT` or `GIT_TRAINER_UI_SCROLLBACK`
+const ENV_PREFIX: &str = "GIT_TRAINER_";
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("While reading {0}: {1}")]
+    IoError(PathBuf, io::Error),
+
+    #[error("While parsing {0}: {1}")]
+    ParseError(PathBuf, toml::de::Error),
+
+    #[error("Invalid configuration: {0}")]
+    InvalidConfig(toml::de::Error),
+}
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct Config {
+    pub paths: PathsConfig,
+    pub docker: DockerConfig,
+    pub intervals: IntervalsConfig,
+    pub ui: UiConfig,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct PathsConfig {
+    pub database: PathBuf,
+    pub schema: PathBuf,
+    pub migrations: PathBuf,
+    pub tasks: PathBuf,
+    pub tests: PathBuf,
+    pub recordings: PathBuf,
+    /// Message catalogs `<locale>.toml`, they override and extend the built-in ones
+    pub locales: PathBuf,
+    /// Sockets of running tasks are created here and mounted into their containers
+    pub runtime: PathBuf,
+    /// Sources of git-trainer the task images are built from, Dockerfiles refer to files relative to it
+    pub build_context: PathBuf,
+    /// Repositories of the tasks by work_name, copied instead of cloning `build.source` from the network
+    pub task_sources: Option<PathBuf>,
+}
+
+impl Default for PathsConfig {
+    fn default() -> Self {
+        #[cfg(debug_assertions)]
+        let paths = PathsConfig {
+            database: PathBuf::from("db.sqlite"),
+            schema: PathBuf::from("schema.sql"),
+            migrations: PathBuf::from("migrations"),
+            tasks: PathBuf::from("tasks"),
+            tests: PathBuf::from("tests"),
+            recordings: PathBuf::from("recordings"),
+            locales: PathBuf::from("locales"),
+            runtime: env::temp_dir().join("git-trainer"),
+            build_context: PathBuf::from("."),
+            task_sources: None,
+        };
+
+        #[cfg(not(debug_assertions))]
+        let paths = PathsConfig {
+            database: PathBuf::from("/var/lib/git-trainer/db.sqlite"),
+            schema: PathBuf::from("/var/lib/git-trainer/schema.sql"),
+            migrations: PathBuf::from("/var/lib/git-trainer/migrations"),
+            tasks: PathBuf::from("/var/lib/git-trainer/tasks"),
+            tests: PathBuf::from("/var/lib/git-trainer/tests"),
+            recordings: PathBuf::from("/var/lib/git-trainer/recordings"),
+            locales: PathBuf::from("/var/lib/git-trainer/locales"),
+            runtime: PathBuf::from("/run/git-trainer"),
+            build_context: PathBuf::from("."),
+            task_sources: None,
+        };
+
+        paths
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct DockerConfig {
+    /// `unix:///path/to/socket` or `tcp://host:port`, the Docker socket by default.
+    /// Podman works through its Docker-compatible socket
+    pub endpoint: Option<String>,
+    /// Task images without an explicit `image` in the manifest are named `<prefix>:<work_name>`
+    pub image_prefix: String,
+}
+
+impl Default for DockerConfig {
+    fn default() -> Self {
+        DockerConfig {
+            endpoint: None,
+            image_prefix: "git-trainer".to_string(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct IntervalsConfig {
+    /// How often the repository graph panel is refreshed
+    pub git_graph_ms: u64,
+    /// How long the task terminal waits for input before redrawing the screen
+    pub input_poll_ms: u64,
+}
+
+impl Default for IntervalsConfig {
+    fn default() -> Self {
+        IntervalsConfig {
+            git_graph_ms: 2000,
+            input_poll_ms: 10,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct UiConfig {
+    /// Language of the interface and of the tasks, e.g. `en`. Taken from `LANG` if not set
+    pub locale: Option<String>,
+    /// Lines of terminal output kept above the screen of a running task
+    pub scrollback: usize,
+    /// Record task sessions in asciicast
+    pub record_sessions: bool,
+    /// Side panels of the task terminal that are open from the start
+    pub task_description: bool,
+    pub git_graph: bool,
+    /// Key after which the next one is a command of the trainer rather than input for the container, like in tmux
+    pub prefix_key: KeyBinding,
+}
+
+impl Default for UiConfig {
+    fn default() -> Self {
+        UiConfig {
+            locale: None,
+            scrollback: 1000,
+            record_sessions: false,
+            task_description: false,
+            git_graph: false,
+            prefix_key: KeyBinding {
+                code: KeyCode::Char('b'),
+                modifiers: KeyModifiers::CONTROL,
+            },
+        }
+    }
+}
+
+fn user_config_path() -> Option<PathBuf> {
+    let config_home = match env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(env::var_os("HOME")?).join(".config"),
+    };
+    Some(config_home.join("git-trainer").join("config.toml"))
+}
+
+fn read_table(path: &Path) -> Result<Option<toml::Table>, ConfigError> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(ConfigError::IoError(path.to_path_buf(), e)),
+    };
+    toml::from_str(&content)
+        .map(Some)
+        .map_err(|e| ConfigError::ParseError(path.to_path_buf(), e))
+}
+
+/// Values of `overlay` replace the ones of `base`, nested tables are merged key by key
+fn merge(base: &mut toml::Table, overlay: toml::Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base)), toml::Value::Table(overlay)) => merge(base, overlay),
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Settings from the environment. Values of string settings are taken as is,
+/// the others are parsed as TOML values, so that `true` or `1000` keep their types
+fn env_overrides(defaults: &toml::Table) -> toml::Table {
+    let mut overrides = toml::Table::new();
+    for (name, raw) in env::vars() {
+        let Some((section, key)) = name
+            .strip_prefix(ENV_PREFIX)
+            .and_then(|rest| rest.split_once('_'))
+        else {
+            continue;
+        };
+        let (section, key) = (section.to_lowercase(), key.to_lowercase());
+        let Some(toml::Value::Table(default_section)) = defaults.get(&section) else {
+            continue;
+        };
+
+        let value = match default_section.get(&key) {
+            Some(toml::Value::String(_)) | None => toml::Value::String(raw),
+            Some(_) => match toml::from_str::<toml::Table>(&format!("value = {}", raw)) {
+                Ok(mut table) => table.remove("value").unwrap_or(toml::Value::String(raw)),
+                Err(_) => toml::Value::String(raw),
+            },
+        };
+
+        if let toml::Value::Table(section) = overrides
+            .entry(section)
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()))
+        {
+            section.insert(key, value);
+        }
+    }
+    overrides
+}
+
+impl Config {
+    /// Defaults, then the system config, then the user config, then the environment
+    pub fn load() -> Result<Config, ConfigError> {
+        let defaults = toml::Table::try_from(Config::default()).unwrap_or_default();
+
+        let mut table = toml::Table::new();
+        let user_config = user_config_path();
+        for path in [Some(PathBuf::from(SYSTEM_CONFIG_PATH)), user_config]
+            .into_iter()
+            .flatten()
+        {
+            if let Some(file_table) = read_table(&path)? {
+                merge(&mut table, file_table);
+            }
+        }
+        merge(&mut table, env_overrides(&defaults));
+
+        toml::Value::Table(table)
+            .try_into()
+            .map_err(ConfigError::InvalidConfig)
+    }
+}
+
+/// Makes the configuration available through `config()`. Has no effect if it is already set
+pub fn init(config: Config) {
+    let _ = CONFIG.set(config);
+}
+
+pub fn config() -> &'static Config {
+    CONFIG.get_or_init(Config::default)
+}
@@ -1,9 +1,15 @@
+use crate::config::config;
+use crate::embedded;
+use crate::locale::tr;
+use crate::manifest::{self, TaskManifest};
+use crate::migrations::{self, MigrationError, MigrationStatus};
 use chrono::{DateTime, Local, ParseError, Utc};
 use core::fmt;
-use rusqlite::{Connection, Result, params};
+use rusqlite::types::{FromSql, FromSqlResult, ToSqlOutput, ValueRef};
+use rusqlite::{Connection, MAIN_DB, OptionalExtension, Result, ToSql, params};
+use serde::Serialize;
 use std::fs;
 use std::path::Path;
-use thiserror::Error;
 
 /// TaskEntity represents single task object in database
 pub struct TaskEntity {
@@ -11,6 +17,20 @@ pub struct TaskEntity {
     pub name: String,
     pub work_name: String,
     pub description: String,
+    pub extended_description: String,
+    pub image: Option<String>,
+    pub author: Option<String>,
+    pub difficulty: Option<String>,
+    pub tags: Vec<String>,
+}
+
+/// Tags are stored comma-separated in a single column
+fn parse_tags(tags: String) -> Vec<String> {
+    tags.split(',')
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty())
+        .map(str::to_string)
+        .collect()
 }
 
 #[derive(Clone)]
@@ -25,11 +45,11 @@ pub enum TaskStatus {
 impl fmt::Display for TaskStatus {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            TaskStatus::NotInProgress => write!(f, "НЕ НАЧАТО"),
-            TaskStatus::InProgress => write!(f, "НАЧАТО"),
-            TaskStatus::Done => write!(f, "ПРОВЕРЕНО"),
-            TaskStatus::Pending => write!(f, "В ПРОВЕРКЕ"),
-            TaskStatus::Approved => write!(f, "СДАНО"),
+            TaskStatus::NotInProgress => write!(f, "{}", tr("task_status.not_in_progress")),
+            TaskStatus::InProgress => write!(f, "{}", tr("task_status.in_progress")),
+            TaskStatus::Done => write!(f, "{}", tr("task_status.done")),
+            TaskStatus::Pending => write!(f, "{}", tr("task_status.pending")),
+            TaskStatus::Approved => write!(f, "{}", tr("task_status.approved")),
         }
     }
 }
@@ -41,16 +61,21 @@ pub struct Task {
     pub image_name: String,
     pub container_name: String,
     pub description: String,
+    pub extended_description: String,
+    pub author: Option<String>,
+    pub difficulty: Option<String>,
+    pub tags: Vec<String>,
     pub status: TaskStatus,
-    pub attempts: Result<Vec<Attempt>>,
+    /// Newest first
+    pub attempts: Vec<Attempt>,
 }
 
 struct AttemptEntity {
     id: i64,
-    user_id: i64,
-    task_id: i64,
     timestamp: String,
     bash_history: String,
+    hints_used: i64,
+    recording: Option<String>,
 }
 
 pub fn format_timestamp(timestamp_str: &str) -> Result<String, ParseError> {
@@ -62,10 +87,13 @@ pub fn format_timestamp(timestamp_str: &str) -> Result<String, ParseError> {
 }
 
 pub struct Attempt {
-    pub id: i64,
     pub timestamp: Result<String, ParseError>,
-    pub tests: Result<Vec<Test>>,
+    pub tests: Vec<Test>,
     pub bash_history: String,
+    /// How many hints of the task had been unlocked when the attempt was submitted
+    pub hints_used: i64,
+    /// Path to the asciicast recording of the session the attempt was submitted from
+    pub recording: Option<String>,
 }
 
 pub struct AttemptCreate {
@@ -73,79 +101,49 @@ pub struct AttemptCreate {
     pub task_id: i64,
     pub tests: Vec<TestCreate>,
     pub bash_history: String,
+    pub hints_used: i64,
+    pub recording: Option<String>,
 }
 
+/// Also sent to the git-trainer CLI as a result of the submitted attempt
+#[derive(Clone, Serialize)]
 pub struct TestCreate {
     pub description: String,
-    pub result: i64,
-}
-
-pub struct NewAttemptEntity {
-    pub tests: Vec<NewTestEntity>,
-}
-
-impl From<AttemptEntity> for Attempt {
-    fn from(attempt_entity: AttemptEntity) -> Self {
-        Attempt {
-            id: attempt_entity.id,
-            timestamp: format_timestamp(&attempt_entity.timestamp),
-            tests: Repo::get_attempt_tests(&Repo::init_database(), attempt_entity.id),
-            bash_history: attempt_entity.bash_history,
-        }
-    }
-}
-
-impl From<Attempt> for NewAttemptEntity {
-    fn from(attempt: Attempt) -> Self {
-        NewAttemptEntity {
-            tests: attempt
-                .tests
-                .expect("While working with db:")
-                .into_iter()
-                .map(NewTestEntity::from)
-                .collect(),
-        }
-    }
-}
-
-pub struct NewTestEntity {
-    description: String,
-    result: i64,
+    pub result: TestResult,
+    pub hint: Option<String>,
+    pub expected: Option<String>,
+    pub actual: Option<String>,
+    pub score: Option<f64>,
 }
 
 struct TestEntity {
     description: String,
-    result: i64,
+    result: TestResult,
+    hint: Option<String>,
+    expected: Option<String>,
+    actual: Option<String>,
+    score: Option<f64>,
 }
 
 #[derive(Clone)]
 pub struct Test {
     pub description: String,
     pub result: TestResult,
+    pub hint: Option<String>,
+    pub expected: Option<String>,
+    pub actual: Option<String>,
+    pub score: Option<f64>,
 }
 
 impl From<TestEntity> for Test {
     fn from(test_entity: TestEntity) -> Self {
         Test {
             description: test_entity.description,
-            result: match test_entity.result {
-                0 => TestResult::Passed,
-                2 => TestResult::NotExecuted,
-                _ => TestResult::Failed,
-            },
-        }
-    }
-}
-
-impl From<Test> for NewTestEntity {
-    fn from(test: Test) -> Self {
-        NewTestEntity {
-            description: test.description,
-            result: match test.result {
-                TestResult::Passed => 0,
-                TestResult::Failed => 1,
-                TestResult::NotExecuted => 2,
-            },
+            result: test_entity.result,
+            hint: test_entity.hint,
+            expected: test_entity.expected,
+            actual: test_entity.actual,
+            score: test_entity.score,
         }
     }
 }
@@ -164,11 +162,38 @@ impl From<UserEntity> for User {
     }
 }
 
-#[derive(PartialEq, Clone)]
+#[derive(PartialEq, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum TestResult {
     Passed,
     Failed,
     NotExecuted,
+    TimedOut,
+}
+
+/// TestResult is stored in attempt_tests.result as 0 (passed), 1 (failed),
+/// 2 (not executed) or 3 (timed out)
+impl ToSql for TestResult {
+    fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
+        let value: i64 = match self {
+            TestResult::Passed => 0,
+            TestResult::Failed => 1,
+            TestResult::NotExecuted => 2,
+            TestResult::TimedOut => 3,
+        };
+        Ok(ToSqlOutput::from(value))
+    }
+}
+
+impl FromSql for TestResult {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        Ok(match value.as_i64()? {
+            0 => TestResult::Passed,
+            2 => TestResult::NotExecuted,
+            3 => TestResult::TimedOut,
+            _ => TestResult::Failed,
+        })
+    }
 }
 
 pub struct Repo {
@@ -192,44 +217,49 @@ impl From<UserTaskStatus> for TaskStatus {
     }
 }
 
-#[derive(Debug, Error)]
-pub enum RunMigrationsError {
-    #[error("No migrations directory found")]
-    NoDirectory(),
-
-    #[error("While working with SQL: {0}")]
-    SQLiteError(#[from] rusqlite::Error),
+fn migrations_dir() -> &'static Path {
+    &config().paths.migrations
 }
 
 impl Repo {
-    pub fn init_database() -> Self {
-        #[cfg(debug_assertions)]
-        let db_path = "db.sqlite";
-
-        #[cfg(not(debug_assertions))]
-        let db_path = "/var/lib/git-trainer/db.sqlite";
+    pub fn init_database() -> Result<Self, MigrationError> {
+        let mut repo = Repo::open()?;
+        repo.migrate_up(None)?;
+        Ok(repo)
+    }
 
-        #[cfg(debug_assertions)]
-        let schema_path = "schema.sql";
+    /// Opens the database and applies schema.sql without running migrations
+    pub fn open() -> Result<Self> {
+        let db_path = &config().paths.database;
+        let schema_path = &config().paths.schema;
 
-        #[cfg(not(debug_assertions))]
-        let schema_path = "/var/lib/git-trainer/schema.sql";
+        let conn = Connection::open(db_path)?;
 
-        let conn = Connection::open(db_path).expect("Failed to connect to db.sqlite");
+        // schema.sql на диске переопределяет вшитую в бинарник схему
+        let schema_sql = match fs::read_to_string(schema_path) {
+            Ok(sql) => sql,
+            Err(_) => embedded::SCHEMA.to_string(),
+        };
 
-        let schema_sql = fs::read_to_string(schema_path).expect("Failed to read schema.sql");
+        conn.execute_batch(&schema_sql)?;
+        Ok(Repo { connection: conn })
+    }
 
-        conn.execute_batch(&schema_sql)
-            .expect("Failed to execute schema.sql");
-        let mut repo = Repo { connection: conn };
-        repo.run_migrations().unwrap();
-        repo
+    /// Attempt with its tests, which are stored in a separate table
+    fn load_attempt(&self, attempt_entity: AttemptEntity) -> Result<Attempt> {
+        Ok(Attempt {
+            timestamp: format_timestamp(&attempt_entity.timestamp),
+            tests: self.get_attempt_tests(attempt_entity.id)?,
+            bash_history: attempt_entity.bash_history,
+            hints_used: attempt_entity.hints_used,
+            recording: attempt_entity.recording,
+        })
     }
 
     pub fn get_task_attempts_user_local(&self, user_id: i64, task_id: i64) -> Result<Vec<Attempt>> {
         let conn = &self.connection;
         let mut stmt = conn.prepare(
-            "SELECT id, user_id, task_id, timestamp, bash_history
+            "SELECT id, timestamp, bash_history, hints_used, recording
          FROM attempts WHERE user_id = ?1 AND task_id = ?2
          ORDER BY timestamp DESC",
         )?;
@@ -246,18 +276,15 @@ impl Repo {
 
         let mut attempts = Vec::new();
         for attempt_row in attempt_rows {
-            let (id, user_id, task_id, timestamp, bash_history) = attempt_row?;
-
-            attempts.push(
-                AttemptEntity {
-                    id,
-                    user_id,
-                    task_id,
-                    timestamp,
-                    bash_history,
-                }
-                .into(),
-            );
+            let (id, timestamp, bash_history, hints_used, recording) = attempt_row?;
+
+            attempts.push(self.load_attempt(AttemptEntity {
+                id,
+                timestamp,
+                bash_history,
+                hints_used,
+                recording,
+            })?);
         }
 
         Ok(attempts)
@@ -288,11 +315,17 @@ impl Repo {
             Ok(Task {
                 id: task_model.id,
                 name: task_model.name,
-                attempts: Ok(attempts),
+                attempts,
                 container_name: format!("git-trainer_{}_{}", task_model.work_name, username),
                 work_name: task_model.work_name.clone(),
-                image_name: format!("git-trainer:{}", task_model.work_name),
+                image_name: task_model
+                    .image
+                    .unwrap_or_else(|| manifest::default_image_name(&task_model.work_name)),
                 description: task_model.description,
+                extended_description: task_model.extended_description,
+                author: task_model.author,
+                difficulty: task_model.difficulty,
+                tags: task_model.tags,
                 status: match user_task_status.status {
                     0 => TaskStatus::NotInProgress,
                     1 => TaskStatus::InProgress,
@@ -318,7 +351,8 @@ impl Repo {
     pub fn get_task_by_id(&self, task_id: i64) -> Result<TaskEntity> {
         let conn = &self.connection;
         conn.query_row(
-            "SELECT id, name, work_name, description, extended_description FROM tasks WHERE id = ?1",
+            "SELECT id, name, work_name, description, extended_description, image, author, difficulty, tags
+             FROM tasks WHERE id = ?1",
             [task_id],
             |row| {
                 Ok(TaskEntity {
@@ -326,6 +360,11 @@ impl Repo {
                     name: row.get(1)?,
                     work_name: row.get(2)?,
                     description: row.get(3)?,
+                    extended_description: row.get(4)?,
+                    image: row.get(5)?,
+                    author: row.get(6)?,
+                    difficulty: row.get(7)?,
+                    tags: parse_tags(row.get(8)?),
                 })
             }
         )
@@ -333,22 +372,85 @@ impl Repo {
 
     pub fn get_all_tasks(&self) -> Result<Vec<TaskEntity>> {
         let conn = &self.connection;
-        let mut stmt = conn.prepare("SELECT * FROM tasks")?;
+        let mut stmt = conn.prepare(
+            "SELECT id, name, work_name, description, extended_description, image, author, difficulty, tags
+             FROM tasks",
+        )?;
         let task_rows = stmt.query_map([], |row| {
-            Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+            Ok(TaskEntity {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                work_name: row.get(2)?,
+                description: row.get(3)?,
+                extended_description: row.get(4)?,
+                image: row.get(5)?,
+                author: row.get(6)?,
+                difficulty: row.get(7)?,
+                tags: parse_tags(row.get(8)?),
+            })
         })?;
-        let mut task_models = Vec::new();
-        for task_row in task_rows {
-            let (id, name, work_name, description) = task_row?;
-            task_models.push(TaskEntity {
-                id,
-                name,
-                work_name,
-                description,
-            });
+
+        task_rows.collect()
+    }
+
+    /// Inserts tasks from manifests that are not in the database yet and updates the existing
+    /// ones, matching them by work_name so that ids of already started tasks never change
+    pub fn sync_tasks(&mut self, manifests: &[TaskManifest]) -> Result<()> {
+        let tx = self.connection.transaction()?;
+
+        for manifest in manifests {
+            let existing_id: Option<i64> = tx
+                .query_row(
+                    "SELECT id FROM tasks WHERE work_name = ?1",
+                    [&manifest.work_name],
+                    |row| row.get(0),
+                )
+                .optional()?;
+
+            let image = manifest.image_name();
+            let tags = manifest.metadata.tags.join(",");
+            let extended_description = manifest.extended_description.trim();
+
+            match existing_id {
+                Some(id) => {
+                    tx.execute(
+                        "UPDATE tasks
+                         SET name = ?1, description = ?2, extended_description = ?3,
+                             image = ?4, author = ?5, difficulty = ?6, tags = ?7
+                         WHERE id = ?8",
+                        params![
+                            manifest.name,
+                            manifest.description,
+                            extended_description,
+                            image,
+                            manifest.metadata.author,
+                            manifest.metadata.difficulty,
+                            tags,
+                            id
+                        ],
+                    )?;
+                }
+                None => {
+                    tx.execute(
+                        "INSERT INTO tasks (name, work_name, description, extended_description,
+                                            image, author, difficulty, tags)
+                         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                        params![
+                            manifest.name,
+                            manifest.work_name,
+                            manifest.description,
+                            extended_description,
+                            image,
+                            manifest.metadata.author,
+                            manifest.metadata.difficulty,
+                            tags
+                        ],
+                    )?;
+                }
+            }
         }
 
-        Ok(task_models)
+        tx.commit()
     }
 
     pub fn load_new_tasks(&self, user_id: i64, loaded_tasks: &Vec<Task>) -> Result<()> {
@@ -383,8 +485,7 @@ impl Repo {
         tx.execute(
             "INSERT INTO users (username, created_at) VALUES (?1, ?2)",
             params![username, now],
-        )
-        .unwrap();
+        )?;
 
         let user_id = tx.last_insert_rowid();
 
@@ -434,18 +535,33 @@ impl Repo {
         let now = Utc::now().to_rfc3339();
 
         tx.execute(
-            "INSERT INTO attempts (user_id, task_id, timestamp, bash_history)
-         VALUES (?1, ?2, ?3, ?4)",
-            params![attempt.user_id, attempt.task_id, now, attempt.bash_history],
+            "INSERT INTO attempts (user_id, task_id, timestamp, bash_history, hints_used, recording)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                attempt.user_id,
+                attempt.task_id,
+                now,
+                attempt.bash_history,
+                attempt.hints_used,
+                attempt.recording
+            ],
         )?;
 
         let attempt_id = tx.last_insert_rowid();
 
         for test in attempt.tests {
             tx.execute(
-                "INSERT INTO attempt_tests (attempt_id, description, result)
-             VALUES (?1, ?2, ?3)",
-                params![attempt_id, test.description, test.result],
+                "INSERT INTO attempt_tests (attempt_id, description, result, hint, expected, actual, score)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    attempt_id,
+                    test.description,
+                    test.result,
+                    test.hint,
+                    test.expected,
+                    test.actual,
+                    test.score
+                ],
             )?;
         }
 
@@ -456,7 +572,7 @@ impl Repo {
     pub fn get_attempt_tests(&self, attempt_id: i64) -> Result<Vec<Test>> {
         let conn = &self.connection;
         let mut stmt = conn.prepare(
-            "SELECT id, attempt_id, description, result
+            "SELECT id, attempt_id, description, result, hint, expected, actual, score
          FROM attempt_tests WHERE attempt_id = ?1
          ORDER BY id",
         )?;
@@ -465,6 +581,10 @@ impl Repo {
             Ok(TestEntity {
                 description: row.get(2)?,
                 result: row.get(3)?,
+                hint: row.get(4)?,
+                expected: row.get(5)?,
+                actual: row.get(6)?,
+                score: row.get(7)?,
             }
             .into())
         })?;
@@ -472,6 +592,55 @@ impl Repo {
         tests.collect()
     }
 
+    /// Number of .bash_history lines already stored in previous attempts
+    pub fn get_history_offset(&self, user_id: i64, task_id: i64) -> Result<i64> {
+        let conn = &self.connection;
+        conn.query_row(
+            "SELECT history_offset FROM user_task_statuses WHERE user_id = ?1 AND task_id = ?2",
+            [user_id, task_id],
+            |row| row.get(0),
+        )
+    }
+
+    pub fn set_history_offset(&self, user_id: i64, task_id: i64, offset: i64) -> Result<()> {
+        let conn = &self.connection;
+        conn.execute(
+            "UPDATE user_task_statuses SET history_offset = ?1 WHERE user_id = ?2 AND task_id = ?3",
+            params![offset, user_id, task_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn get_unlocked_hints_count(&self, user_id: i64, task_id: i64) -> Result<i64> {
+        let conn = &self.connection;
+        conn.query_row(
+            "SELECT COUNT(*) FROM unlocked_hints WHERE user_id = ?1 AND task_id = ?2",
+            [user_id, task_id],
+            |row| row.get(0),
+        )
+    }
+
+    pub fn unlock_hint(&self, user_id: i64, task_id: i64, hint_index: i64) -> Result<()> {
+        let conn = &self.connection;
+        conn.execute(
+            "INSERT OR IGNORE INTO unlocked_hints (user_id, task_id, hint_index, unlocked_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![user_id, task_id, hint_index, Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    /// Whether attempts can be saved: false if the database is read-only,
+    /// an error if another process holds a lock on it
+    pub fn check_writable(&self) -> Result<bool> {
+        if self.connection.is_readonly(MAIN_DB)? {
+            return Ok(false);
+        }
+        self.connection
+            .execute_batch("BEGIN IMMEDIATE; ROLLBACK;")?;
+        Ok(true)
+    }
+
     pub fn update_task_status(&self, task_id: i64, user_id: i64, status: TaskStatus) -> Result<()> {
         let conn = &self.connection;
         let status = match status {
@@ -489,80 +658,18 @@ impl Repo {
         Ok(())
     }
 
-    pub fn run_migrations(&mut self) -> Result<(), RunMigrationsError> {
-        #[cfg(debug_assertions)]
-        let migrations_dir = "migrations";
-
-        #[cfg(not(debug_assertions))]
-        let migrations_dir = "/var/lib/git-trainer/migrations";
-
-        let migrations_dir = Path::new(migrations_dir);
-        if !migrations_dir.exists() {
-            return Err(RunMigrationsError::NoDirectory());
-        }
-
-        self.connection.execute_batch(
-            "CREATE TABLE IF NOT EXISTS schema_migrations (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                migration_name TEXT NOT NULL UNIQUE,
-                applied_at TEXT NOT NULL
-            )",
-        )?;
-
-        let read_dir = match std::fs::read_dir(migrations_dir) {
-            Ok(dir) => dir,
-            Err(_) => return Ok(()),
-        };
-
-        for entry_result in read_dir {
-            let entry = match entry_result {
-                Ok(entry) => entry,
-                Err(_) => continue,
-            };
-
-            if !entry.path().is_dir() {
-                continue;
-            }
-
-            let mig_name = entry.file_name().to_string_lossy().to_string();
-
-            if self.is_migration_applied(&mig_name)? {
-                continue;
-            }
-
-            let tx = self.connection.transaction()?;
-            let up_path = entry.path().join("up.sql");
-
-            if !up_path.exists() {
-                let _ = tx.rollback();
-                continue;
-            }
-
-            let up_sql = match std::fs::read_to_string(&up_path) {
-                Ok(sql) => sql,
-                Err(_) => {
-                    let _ = tx.rollback();
-                    continue;
-                }
-            };
-
-            tx.execute_batch(&up_sql)?;
-
-            let now = Utc::now().to_rfc3339();
-            tx.execute(
-                "INSERT INTO schema_migrations (migration_name, applied_at) VALUES (?1, ?2)",
-                params![&mig_name, &now],
-            )?;
+    pub fn migrate_up(&mut self, target: Option<i64>) -> Result<Vec<String>, MigrationError> {
+        let migrations = migrations::load_migrations(migrations_dir())?;
+        migrations::migrate_up(&mut self.connection, &migrations, target)
+    }
 
-            tx.commit()?;
-        }
-        Ok(())
+    pub fn migrate_down(&mut self, steps: usize) -> Result<Vec<String>, MigrationError> {
+        let migrations = migrations::load_migrations(migrations_dir())?;
+        migrations::migrate_down(&mut self.connection, &migrations, steps)
     }
 
-    fn is_migration_applied(&self, name: &str) -> Result<bool> {
-        let mut stmt = self
-            .connection
-            .prepare("SELECT 1 FROM schema_migrations WHERE migration_name = ?1")?;
-        stmt.exists(params![name])
+    pub fn migration_status(&mut self) -> Result<Vec<MigrationStatus>, MigrationError> {
+        let migrations = migrations::load_migrations(migrations_dir())?;
+        migrations::migration_status(&mut self.connection, &migrations)
     }
 }
@@ -1,47 +1,218 @@
+use crate::config::config;
 use crate::db::Task;
-use bollard::Docker;
+use crate::events::{self, CONTAINER_SOCKET_DIR};
+use crate::locale;
 use bollard::body_full;
 use bollard::container::AttachContainerResults;
 use bollard::exec::{CreateExecOptions, StartExecResults};
-use bollard::models::ContainerCreateBody;
+use bollard::models::{ContainerConfig, ContainerCreateBody, ContainerInspectResponse, HostConfig};
 use bollard::query_parameters::{
-    AttachContainerOptionsBuilder, CreateContainerOptionsBuilder, InspectContainerOptions,
-    RemoveContainerOptionsBuilder, ResizeContainerTTYOptionsBuilder, StartContainerOptionsBuilder,
+    AttachContainerOptionsBuilder, BuildImageOptionsBuilder, CommitContainerOptionsBuilder,
+    CreateContainerOptionsBuilder, DownloadFromContainerOptionsBuilder, InspectContainerOptions,
+    RemoveContainerOptionsBuilder, RemoveImageOptionsBuilder, ResizeContainerTTYOptionsBuilder,
+    StartContainerOptionsBuilder, StopContainerOptionsBuilder, TagImageOptionsBuilder,
     UploadToContainerOptionsBuilder,
 };
+use bollard::{API_DEFAULT_VERSION, Docker};
 use bytes::Bytes;
-use tar::Builder;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use tar::{Builder, Header};
 
 use futures_util::StreamExt;
 
+/// Where the CLI inside the container reads the statement of the task from
+const DESCRIPTION_DIR: &str = "/etc/git-trainer";
+const DESCRIPTION_FILE_NAME: &str = "description";
+
+/// Seconds a request to the Docker API may take
+const DOCKER_TIMEOUT_SECS: u64 = 120;
+
+/// ID of the image the task container was created from
+const IMAGE_ID_LABEL: &str = "git-trainer.image-id";
+/// Version of what git-trainer itself put into the task container, see `task_version`
+const TASK_VERSION_LABEL: &str = "git-trainer.task-version";
+
 fn docker_connect() -> Result<Docker, bollard::errors::Error> {
-    Docker::connect_with_socket_defaults()
+    match &config().docker.endpoint {
+        None => Docker::connect_with_socket_defaults(),
+        Some(endpoint) if endpoint.starts_with("tcp://") || endpoint.starts_with("http://") => {
+            Docker::connect_with_http(endpoint, DOCKER_TIMEOUT_SECS, API_DEFAULT_VERSION)
+        }
+        Some(endpoint) => {
+            Docker::connect_with_socket(endpoint, DOCKER_TIMEOUT_SECS, API_DEFAULT_VERSION)
+        }
+    }
 }
 
-pub async fn ensure_task_container_running(task: &Task) -> Result<(), bollard::errors::Error> {
+/// Version of the Docker daemon, also tells whether it is reachable at all
+pub async fn daemon_version() -> Result<String, bollard::errors::Error> {
     let docker = docker_connect()?;
+    let version = docker.version().await?;
+    Ok(version.version.unwrap_or_default())
+}
 
-    let exists = match docker
-        .inspect_container(
-            &task.container_name.clone(),
-            None::<InspectContainerOptions>,
-        )
+pub async fn image_exists(image_name: &str) -> Result<bool, bollard::errors::Error> {
+    let docker = docker_connect()?;
+    match docker.inspect_image(image_name).await {
+        Ok(_) => Ok(true),
+        Err(bollard::errors::Error::DockerResponseServerError {
+            status_code: 404, ..
+        }) => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+/// Value of the label of a local image, None if there is no such image or label
+pub async fn image_label(
+    image_name: &str,
+    label: &str,
+) -> Result<Option<String>, bollard::errors::Error> {
+    let docker = docker_connect()?;
+    match docker.inspect_image(image_name).await {
+        Ok(info) => Ok(info
+            .config
+            .and_then(|config| config.labels)
+            .and_then(|mut labels| labels.remove(label))),
+        Err(bollard::errors::Error::DockerResponseServerError {
+            status_code: 404, ..
+        }) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// What to build an image from, the Dockerfile path is relative to the context
+pub struct ImageBuild<'a> {
+    pub context: Vec<u8>,
+    pub dockerfile: &'a str,
+    pub image_name: &'a str,
+    pub build_args: HashMap<String, String>,
+    pub labels: HashMap<String, String>,
+}
+
+/// Builds the image from a tar'd context, every line of the build output is passed to `on_output`
+pub async fn build_image(
+    build: ImageBuild<'_>,
+    mut on_output: impl FnMut(String),
+) -> Result<(), bollard::errors::Error> {
+    let docker = docker_connect()?;
+    let options = BuildImageOptionsBuilder::new()
+        .dockerfile(build.dockerfile)
+        .t(build.image_name)
+        .buildargs(&build.build_args)
+        .labels(&build.labels)
+        .rm(true)
+        .forcerm(true)
+        .build();
+
+    let mut stream = docker.build_image(options, None, Some(body_full(Bytes::from(build.context))));
+    while let Some(info) = stream.next().await {
+        let info = info?;
+        if let Some(error) = info
+            .error_detail
+            .and_then(|detail| detail.message)
+            .or(info.error)
+        {
+            return Err(bollard::errors::Error::DockerStreamError { error });
+        }
+        let line = info.stream.or(info.status).unwrap_or_default();
+        for line in line.lines().filter(|line| !line.trim().is_empty()) {
+            on_output(line.to_string());
+        }
+    }
+    Ok(())
+}
+
+/// Adds one more `repo:tag` name to a local image
+pub async fn tag_image(
+    image_name: &str,
+    repo: &str,
+    tag: &str,
+) -> Result<(), bollard::errors::Error> {
+    let docker = docker_connect()?;
+    let options = TagImageOptionsBuilder::new().repo(repo).tag(tag).build();
+    docker.tag_image(image_name, Some(options)).await
+}
+
+/// The statement and the locale are written into the container when it is created,
+/// so a container made for another version of them is outdated as well
+fn task_version(task: &Task) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(task.extended_description.as_bytes());
+    hasher.update([0]);
+    hasher.update(locale::current().as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+pub enum TaskContainer {
+    /// Attached before the start, so that no output of the shell is lost
+    Running(AttachContainerResults),
+    /// Created from another image or task version, it is left stopped
+    Outdated,
+}
+
+/// Creates the container of the task if there is none, checks that it matches the current
+/// image and task version unless `check_version` is false, then attaches to it and starts it
+pub async fn ensure_task_container_running(
+    task: &Task,
+    check_version: bool,
+) -> Result<TaskContainer, bollard::errors::Error> {
+    let docker = docker_connect()?;
+
+    match docker
+        .inspect_container(&task.container_name, None::<InspectContainerOptions>)
         .await
     {
-        Ok(_) => true,
+        Ok(info) => {
+            if check_version && is_outdated(&docker, task, info).await? {
+                return Ok(TaskContainer::Outdated);
+            }
+        }
         Err(bollard::errors::Error::DockerResponseServerError {
             status_code: 404, ..
-        }) => false,
+        }) => {
+            create_task_container(task).await?;
+        }
         Err(e) => return Err(e),
-    };
-
-    if !exists {
-        ensure_task_container_created(task).await?;
     }
 
+    let attached = attach_container(task).await?;
     start_container(task).await?;
+    Ok(TaskContainer::Running(attached))
+}
 
-    Ok(())
+/// Containers created before they were labeled have no task version, only their image is compared
+async fn is_outdated(
+    docker: &Docker,
+    task: &Task,
+    info: ContainerInspectResponse,
+) -> Result<bool, bollard::errors::Error> {
+    let image_id = match docker.inspect_image(&task.image_name).await {
+        Ok(image) => image.id,
+        // Без образа пересоздать контейнер всё равно не выйдет
+        Err(bollard::errors::Error::DockerResponseServerError {
+            status_code: 404, ..
+        }) => return Ok(false),
+        Err(e) => return Err(e),
+    };
+    if image_id.is_some() && info.image != image_id {
+        return Ok(true);
+    }
+
+    let version = info
+        .config
+        .and_then(|config| config.labels)
+        .and_then(|mut labels| labels.remove(TASK_VERSION_LABEL));
+    Ok(version.is_some_and(|version| version != task_version(task)))
+}
+
+/// The CLI and the tests inside the container speak the language of the interface
+fn locale_env() -> Vec<String> {
+    vec![format!("{}={}", locale::LOCALE_ENV, locale::current())]
 }
 
 pub async fn create_task_container(task: &Task) -> Result<String, bollard::errors::Error> {
@@ -50,42 +221,50 @@ pub async fn create_task_container(task: &Task) -> Result<String, bollard::error
         .name(&task.container_name)
         .build();
 
+    // Через эту директорию CLI внутри контейнера связывается с git-trainer
+    let socket_dir = events::socket_dir(&task.container_name);
+    fs::create_dir_all(&socket_dir)?;
+
+    let image_id = docker.inspect_image(&task.image_name).await?.id;
+    let labels = HashMap::from([
+        (IMAGE_ID_LABEL.to_string(), image_id.unwrap_or_default()),
+        (TASK_VERSION_LABEL.to_string(), task_version(task)),
+    ]);
+
     let config = ContainerCreateBody {
         image: Some(task.image_name.clone()),
+        labels: Some(labels),
         tty: Some(true),
         hostname: Some(task.work_name.clone()),
         attach_stdin: Some(true),
         attach_stdout: Some(true),
         attach_stderr: Some(true),
         open_stdin: Some(true),
+        env: Some(locale_env()),
         // cmd: Some(vec!["bash".into()]),
+        host_config: Some(HostConfig {
+            binds: Some(vec![format!(
+                "{}:{}",
+                socket_dir.display(),
+                CONTAINER_SOCKET_DIR
+            )]),
+            ..Default::default()
+        }),
         ..Default::default()
     };
 
     let created = docker.create_container(Some(create_opts), config).await?;
 
-    Ok(created.id)
-}
-
-pub async fn ensure_task_container_created(task: &Task) -> Result<String, bollard::errors::Error> {
-    let docker = docker_connect()?;
+    // Условие берётся из базы, чтобы не дублировать его в каждом Dockerfile
+    copy_file(
+        &task.container_name,
+        DESCRIPTION_DIR,
+        DESCRIPTION_FILE_NAME,
+        task.extended_description.as_bytes(),
+    )
+    .await?;
 
-    // eprintln!("{}", &task.work_name);
-    match docker
-        .inspect_container(&task.container_name, None::<InspectContainerOptions>)
-        .await
-    {
-        Ok(info) => {
-            return Ok(info.id.unwrap());
-        }
-        Err(bollard::errors::Error::DockerResponseServerError {
-            status_code: 404, ..
-        }) => {}
-        Err(e) => {
-            return Err(e);
-        }
-    }
-    create_task_container(task).await
+    Ok(created.id)
 }
 
 pub async fn delete_task_container(task: &Task) -> Result<(), bollard::errors::Error> {
@@ -110,6 +289,79 @@ pub async fn restart_task(task: &Task) -> Result<(), bollard::errors::Error> {
     Ok(())
 }
 
+/// Recreates the container of the task from the current image. With `keep_path` that
+/// directory of the old container, e.g. the repository of the student, replaces the one of the new
+pub async fn recreate_task_container(
+    task: &Task,
+    keep_path: Option<&str>,
+) -> Result<(), bollard::errors::Error> {
+    let saved = match keep_path {
+        Some(path) => Some((path, download_directory(&task.container_name, path).await?)),
+        None => None,
+    };
+    restart_task(task).await?;
+    let Some((path, archive)) = saved else {
+        return Ok(());
+    };
+
+    // Очистить папку из образа можно только в запущенном контейнере. Удаляется только
+    // содержимое: домашняя папка — рабочая директория оболочки контейнера, а файлы
+    // из образа, которых не было в старом контейнере, не должны остаться среди сохранённых
+    let docker = docker_connect()?;
+    start_container(task).await?;
+    // От root: в свежем образе могут быть файлы, которые USER образа удалить не может
+    let clean = exec_args(
+        &task.container_name,
+        Some("root"),
+        vec!["find", path, "-mindepth", "1", "-delete"],
+    )
+    .await?;
+    if clean.exit_code != 0 {
+        return Err(bollard::errors::Error::DockerContainerWaitError {
+            error: format!("Failed to clean {}: {}", path, clean.output.trim()),
+            code: clean.exit_code,
+        });
+    }
+    let parent = Path::new(path).parent().map_or("/".to_string(), |parent| {
+        parent.to_string_lossy().to_string()
+    });
+    // Файлы получают владельца из USER образа, а не root
+    let options = UploadToContainerOptionsBuilder::new()
+        .path(&parent)
+        .copy_uidgid("true")
+        .build();
+    docker
+        .upload_to_container(
+            &task.container_name,
+            Some(options),
+            body_full(Bytes::from(archive)),
+        )
+        .await?;
+
+    let stop_opts = StopContainerOptionsBuilder::new().t(0).build();
+    docker
+        .stop_container(&task.container_name, Some(stop_opts))
+        .await
+}
+
+/// Tar of a directory of the container, the directory itself is its top entry
+async fn download_directory(
+    container_name: &str,
+    path: &str,
+) -> Result<Vec<u8>, bollard::errors::Error> {
+    let docker = docker_connect()?;
+    let options = DownloadFromContainerOptionsBuilder::new()
+        .path(path)
+        .build();
+    let mut stream = docker.download_from_container(container_name, Some(options));
+
+    let mut archive = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        archive.extend_from_slice(&chunk?);
+    }
+    Ok(archive)
+}
+
 pub async fn start_container(task: &Task) -> Result<(), bollard::errors::Error> {
     let docker = docker_connect()?;
     let start_opts = StartContainerOptionsBuilder::new().build();
@@ -155,17 +407,30 @@ pub struct CmdOutput {
     pub exit_code: i64,
 }
 
-pub async fn exec_command(task: &Task, cmd: &str) -> Result<CmdOutput, bollard::errors::Error> {
+pub async fn exec_command(
+    container_name: &str,
+    cmd: &str,
+) -> Result<CmdOutput, bollard::errors::Error> {
+    exec_args(container_name, None, cmd.split_whitespace().collect()).await
+}
+
+/// Like `exec_command`, but the arguments are passed as is, without splitting on whitespace.
+/// Runs as `user` if given, otherwise as the `USER` of the image
+async fn exec_args(
+    container_name: &str,
+    user: Option<&str>,
+    cmd_string: Vec<&str>,
+) -> Result<CmdOutput, bollard::errors::Error> {
     let docker = docker_connect()?;
-    let cmd_string: Vec<&str> = cmd.split_whitespace().collect();
 
     let exec = docker
         .create_exec(
-            &task.container_name,
+            container_name,
             CreateExecOptions {
                 attach_stdout: Some(true),
                 attach_stderr: Some(true),
                 cmd: Some(cmd_string),
+                user,
                 ..Default::default()
             },
         )
@@ -193,9 +458,200 @@ pub async fn exec_command(task: &Task, cmd: &str) -> Result<CmdOutput, bollard::
     })
 }
 
+/// Extra time given to `timeout` inside the container before the exec is abandoned
+const KILL_GRACE: Duration = Duration::from_secs(5);
+
+/// Makes pid files of concurrent execs with a timeout distinct
+static EXEC_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+pub enum ExecOutcome {
+    Finished(CmdOutput),
+    /// The command was killed after the timeout, contains the output it had printed
+    TimedOut(String),
+}
+
+/// Runs the command under coreutils `timeout`, which puts the command into its own
+/// process group and kills the whole group on expiry. If even that does not return
+/// in time, the exec is abandoned and the group is killed by the id saved in a pid file.
+/// Whether the command timed out is decided by the elapsed time, not by its exit code,
+/// which the command could return on its own
+pub async fn exec_command_with_timeout(
+    container_name: &str,
+    cmd: &str,
+    timeout: Duration,
+) -> Result<ExecOutcome, bollard::errors::Error> {
+    // timeout понимает дробные секунды, а 0 у него означает отсутствие ограничения.
+    // Ограничение округляется вверх до миллисекунд, и дальше используется только оно
+    let millis = timeout.as_nanos().div_ceil(1_000_000).max(1) as u64;
+    let timeout = Duration::from_millis(millis);
+    let secs = format!("{}.{:03}", millis / 1000, millis % 1000);
+    let pid_file = format!(
+        "/tmp/git-trainer-exec-{}.pid",
+        EXEC_COUNTER.fetch_add(1, Ordering::Relaxed)
+    );
+    // После exec pid оболочки становится pid самого timeout, то есть id его группы процессов
+    let mut wrapped = vec![
+        "sh",
+        "-c",
+        "echo $$ > \"$1\"; shift; exec timeout --signal=KILL \"$@\"",
+        "sh",
+        &pid_file,
+        &secs,
+    ];
+    wrapped.extend(cmd.split_whitespace());
+
+    let started = Instant::now();
+    match tokio::time::timeout(
+        timeout + KILL_GRACE,
+        exec_args(container_name, None, wrapped),
+    )
+    .await
+    {
+        // timeout, убивший группу процессов вместе с собой, завершается с кодом 137,
+        // а переживший её — с кодом 124. Такой же код может вернуть и сама команда,
+        // поэтому ещё проверяется, что ограничение действительно истекло
+        Ok(Ok(output)) if is_timeout_exit(output.exit_code) && started.elapsed() >= timeout => {
+            Ok(ExecOutcome::TimedOut(output.output))
+        }
+        Ok(res) => res.map(ExecOutcome::Finished),
+        Err(_) => {
+            let kill = vec!["sh", "-c", "kill -KILL -\"$(cat \"$1\")\"", "sh", &pid_file];
+            let _ = exec_args(container_name, None, kill).await;
+            Ok(ExecOutcome::TimedOut(String::new()))
+        }
+    }
+}
+
+fn is_timeout_exit(exit_code: i64) -> bool {
+    matches!(exit_code, 124 | 137)
+}
+
+/// Repository of temporary images the student containers are committed to before grading
+fn grading_image_repo() -> String {
+    format!("{}-grading", config().docker.image_prefix)
+}
+
+/// Throwaway container started from a commit of the student container. Tests are run
+/// there, so they can neither change the student's working state nor be seen by the student
+pub struct GradingSnapshot {
+    pub container_name: String,
+    image: String,
+}
+
+pub async fn create_grading_snapshot(
+    container_name: &str,
+    hostname: &str,
+) -> Result<GradingSnapshot, bollard::errors::Error> {
+    let docker = docker_connect()?;
+    let snapshot = GradingSnapshot {
+        container_name: format!("{}_grading", container_name),
+        image: format!("{}:{}", grading_image_repo(), container_name),
+    };
+
+    // Снимок мог остаться от проверки, прерванной на середине
+    remove_grading_snapshot(&snapshot).await?;
+
+    let repo = grading_image_repo();
+    let commit_opts = CommitContainerOptionsBuilder::new()
+        .container(container_name)
+        .repo(&repo)
+        .tag(container_name)
+        .pause(true)
+        .build();
+    docker
+        .commit_container(commit_opts, ContainerConfig::default())
+        .await?;
+
+    let create_opts = CreateContainerOptionsBuilder::new()
+        .name(&snapshot.container_name)
+        .build();
+    let config = ContainerCreateBody {
+        image: Some(snapshot.image.clone()),
+        hostname: Some(hostname.to_string()),
+        entrypoint: Some(vec!["sleep".into(), "infinity".into()]),
+        cmd: Some(Vec::new()),
+        env: Some(locale_env()),
+        network_disabled: Some(true),
+        ..Default::default()
+    };
+    docker.create_container(Some(create_opts), config).await?;
+
+    let start_opts = StartContainerOptionsBuilder::new().build();
+    docker
+        .start_container(&snapshot.container_name, Some(start_opts))
+        .await?;
+
+    Ok(snapshot)
+}
+
+/// Removes the snapshot container together with its image, missing ones are skipped
+pub async fn remove_grading_snapshot(
+    snapshot: &GradingSnapshot,
+) -> Result<(), bollard::errors::Error> {
+    let docker = docker_connect()?;
+
+    let remove_container_opts = RemoveContainerOptionsBuilder::new().force(true).build();
+    match docker
+        .remove_container(&snapshot.container_name, Some(remove_container_opts))
+        .await
+    {
+        Ok(_)
+        | Err(bollard::errors::Error::DockerResponseServerError {
+            status_code: 404, ..
+        }) => {}
+        Err(e) => return Err(e),
+    }
+
+    let remove_image_opts = RemoveImageOptionsBuilder::new().force(true).build();
+    match docker
+        .remove_image(&snapshot.image, Some(remove_image_opts), None)
+        .await
+    {
+        Ok(_)
+        | Err(bollard::errors::Error::DockerResponseServerError {
+            status_code: 404, ..
+        }) => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Writes a single file with the given contents into a directory of the container
+pub async fn copy_file(
+    container_name: &str,
+    target_dir: &str,
+    file_name: &str,
+    contents: &[u8],
+) -> Result<(), bollard::errors::Error> {
+    let docker = docker_connect()?;
+    let mut tar_data = Vec::new();
+    {
+        let mut builder = Builder::new(&mut tar_data);
+        let mut header = Header::new_gnu();
+        header.set_size(contents.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder.append_data(&mut header, file_name, contents)?;
+        builder.finish()?;
+    }
+
+    let options = UploadToContainerOptionsBuilder::new()
+        .path(target_dir)
+        .build();
+
+    docker
+        .upload_to_container(
+            container_name,
+            Some(options),
+            body_full(Bytes::from(tar_data)),
+        )
+        .await?;
+
+    Ok(())
+}
+
 pub async fn copy_directory(
     container_name: &str,
-    source_dir: &str,
+    source_dir: &Path,
     target_path: &str,
 ) -> Result<(), bollard::errors::Error> {
     let docker = docker_connect()?;
@@ -0,0 +1,10 @@
+// Схема, миграции, манифесты заданий и каталоги сообщений, вшитые в бинарник с помощью build.rs.
+// Файлы на диске, если они есть, имеют приоритет над ними.
+
+pub struct EmbeddedMigration {
+    pub name: &'static str,
+    pub up: &'static str,
+    pub down: Option<&'static str>,
+}
+
+include!(concat!(env!("OUT_DIR"), "/embedded.rs"));
@@ -0,0 +1,56 @@
+use crate::config::ConfigError;
+use crate::images::BuildError;
+use crate::locale::{LocaleError, tr, tr_fmt};
+use crate::manifest::LoadManifestError;
+use crate::migrations::MigrationError;
+use crate::player::ui::PlayRecordingError;
+use crate::pty::ui::PreparePtyError;
+use std::io;
+use std::path::PathBuf;
+use thiserror::Error;
+
+/// Every failure of git-trainer. Errors while the TUI is running are shown
+/// in `Popup::Error`, errors at startup on a screen of their own.
+/// Messages come from the locale catalog, errors of other modules are shown as is
+#[derive(Debug, Error)]
+#[allow(clippy::enum_variant_names)]
+pub enum AppError {
+    #[error("{0}")]
+    ConfigError(#[from] ConfigError),
+
+    #[error("{0}")]
+    LocaleError(#[from] LocaleError),
+
+    #[error("{}", tr_fmt("errors.database", &[("error", .0)]))]
+    DatabaseError(#[from] rusqlite::Error),
+
+    #[error("{0}")]
+    MigrationError(#[from] MigrationError),
+
+    #[error("{0}")]
+    ManifestError(#[from] LoadManifestError),
+
+    #[error("{}", tr_fmt("errors.docker", &[("error", .0)]))]
+    DockerError(#[from] bollard::errors::Error),
+
+    #[error("{0}")]
+    BuildError(#[from] BuildError),
+
+    #[error("{0}")]
+    PtyError(#[from] PreparePtyError),
+
+    #[error("{0}")]
+    PlayRecordingError(#[from] PlayRecordingError),
+
+    #[error("{}", tr_fmt("errors.username", &[("error", .0)]))]
+    UsernameError(#[from] whoami::Error),
+
+    #[error("{}", tr_fmt("errors.missing_tests", &[("task", .0), ("path", &.1.display()), ("error", .2)]))]
+    MissingTestsError(String, PathBuf, io::Error),
+
+    #[error("{}", tr("errors.no_tasks"))]
+    NoTasksError,
+
+    #[error("{}", tr_fmt("errors.io", &[("error", .0)]))]
+    IoError(#[from] io::Error),
+}
@@ -0,0 +1,143 @@
+use crate::config::config;
+use crate::db::TestCreate;
+use crate::locale::tr_fmt;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::os::unix::fs::PermissionsExt;
+use std::path::PathBuf;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::unix::OwnedWriteHalf;
+use tokio::net::{UnixListener, UnixStream};
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+
+/// Where the socket directory is mounted inside task containers
+pub const CONTAINER_SOCKET_DIR: &str = "/run/git-trainer";
+pub const SOCKET_NAME: &str = "git-trainer.sock";
+
+/// Request of the git-trainer CLI, sent as a single JSON line
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Request {
+    Submit,
+    Restart,
+    Hint,
+}
+
+/// Every request is answered with Ack as soon as git-trainer has queued it,
+/// then with the final response, if the request has one
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Response {
+    Ack,
+    /// Results of all tests of the graded attempt in declaration order
+    Result {
+        tests: Vec<TestCreate>,
+    },
+    /// All unlocked hints, `revealed` is false if there was no hint left to unlock
+    Hints {
+        hints: Vec<String>,
+        total: usize,
+        revealed: bool,
+    },
+    Error {
+        message: String,
+    },
+}
+
+pub struct Event {
+    pub request: Request,
+    /// Dropping it without sending closes the connection after Ack
+    pub reply: oneshot::Sender<Response>,
+}
+
+/// Host directory that is bind-mounted into the container as CONTAINER_SOCKET_DIR
+pub fn socket_dir(container_name: &str) -> PathBuf {
+    config().paths.runtime.join(container_name)
+}
+
+/// Accepts CLI connections of one container while the terminal of the task is open.
+/// The socket is removed when the listener is dropped
+pub struct EventListener {
+    pub events: mpsc::Receiver<Event>,
+    socket_path: PathBuf,
+    handle: JoinHandle<()>,
+}
+
+impl EventListener {
+    pub fn bind(container_name: &str) -> io::Result<EventListener> {
+        let dir = socket_dir(container_name);
+        fs::create_dir_all(&dir)?;
+        fs::set_permissions(&dir, fs::Permissions::from_mode(0o700))?;
+
+        let socket_path = dir.join(SOCKET_NAME);
+        // Сокет мог остаться от прошлого запуска, который не завершился штатно
+        let _ = fs::remove_file(&socket_path);
+        let listener = UnixListener::bind(&socket_path)?;
+        // Подключаться может только setuid-утилита git-trainer: в контейнере она работает
+        // от root, а пользователь задания не может слать запросы в обход неё
+        fs::set_permissions(&socket_path, fs::Permissions::from_mode(0o600))?;
+
+        let (tx, events) = mpsc::channel(16);
+        let handle = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(handle_connection(stream, tx.clone()));
+            }
+        });
+
+        Ok(EventListener {
+            events,
+            socket_path,
+            handle,
+        })
+    }
+}
+
+impl Drop for EventListener {
+    fn drop(&mut self) {
+        self.handle.abort();
+        let _ = fs::remove_file(&self.socket_path);
+    }
+}
+
+async fn send(writer: &mut OwnedWriteHalf, response: &Response) -> io::Result<()> {
+    let mut line = serde_json::to_string(response)?;
+    line.push('\n');
+    writer.write_all(line.as_bytes()).await?;
+    writer.flush().await
+}
+
+async fn handle_connection(stream: UnixStream, events: mpsc::Sender<Event>) {
+    let (reader, mut writer) = stream.into_split();
+
+    let mut line = String::new();
+    if BufReader::new(reader).read_line(&mut line).await.is_err() {
+        return;
+    }
+
+    let request = match serde_json::from_str::<Request>(line.trim()) {
+        Ok(request) => request,
+        Err(e) => {
+            let message = tr_fmt("events.unknown_request", &[("error", &e)]);
+            let _ = send(&mut writer, &Response::Error { message }).await;
+            return;
+        }
+    };
+
+    // Место в очереди занимается до Ack, чтобы не подтверждать запрос, который никто
+    // не обработает, а сам запрос попадает в очередь после Ack: при перезагрузке контейнер
+    // будет удалён сразу
+    let Ok(permit) = events.reserve().await else {
+        return;
+    };
+    if send(&mut writer, &Response::Ack).await.is_err() {
+        return;
+    }
+
+    let (reply, response) = oneshot::channel();
+    permit.send(Event { request, reply });
+    if let Ok(response) = response.await {
+        let _ = send(&mut writer, &response).await;
+    }
+}
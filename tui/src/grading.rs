@@ -0,0 +1,202 @@
+use crate::db::{TestCreate, TestResult};
+use crate::locale::{self, DEFAULT_LOCALE, tr_fmt};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Only lines starting with it are read as reports. Tests print text the student controls,
+/// such as commit messages, so a bare JSON line in the output proves nothing
+const REPORT_PREFIX: &str = "git-trainer-report:";
+
+/// TestReport is a structured result a test script may print as a single line after REPORT_PREFIX, e.g.
+/// git-trainer-report: {"status": "failed", "message": "3. В репозитории должен быть ровно один коммит.",
+///  "hint": "Посмотрите на git log", "expected": "1", "actual": "2", "score": 0}
+/// `message` and `hint` may also be given per locale: {"ru": "...", "en": "..."}
+#[derive(Deserialize)]
+struct TestReport {
+    status: ReportStatus,
+    message: Option<LocalizedText>,
+    hint: Option<LocalizedText>,
+    expected: Option<String>,
+    actual: Option<String>,
+    score: Option<f64>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum LocalizedText {
+    Plain(String),
+    PerLocale(HashMap<String, String>),
+}
+
+impl LocalizedText {
+    /// Text in the current locale, otherwise in the default one, otherwise in any
+    fn resolve(self) -> String {
+        match self {
+            LocalizedText::Plain(text) => text,
+            LocalizedText::PerLocale(mut texts) => texts
+                .remove(locale::current())
+                .or_else(|| texts.remove(DEFAULT_LOCALE))
+                .or_else(|| texts.into_values().next())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ReportStatus {
+    Passed,
+    Failed,
+}
+
+/// Turns the output of a test script into a test result. If the script printed
+/// a TestReport line, the last such line is used and everything else becomes
+/// the message unless the report has its own. Otherwise the whole output is the
+/// description and the exit code decides the result, as for plain testN.sh scripts.
+/// A test that exited with an error fails whatever its report says
+pub fn parse_test_output(output: &str, exit_code: i64) -> TestCreate {
+    let mut report = None;
+    let mut plain_lines = Vec::new();
+
+    for line in output.lines() {
+        if let Some(json) = line.trim().strip_prefix(REPORT_PREFIX)
+            && let Ok(parsed) = serde_json::from_str::<TestReport>(json.trim())
+        {
+            report = Some(parsed);
+            continue;
+        }
+        plain_lines.push(line);
+    }
+
+    match report {
+        Some(report) => TestCreate {
+            description: report
+                .message
+                .map(LocalizedText::resolve)
+                .unwrap_or_else(|| plain_lines.join("\n").trim().to_string()),
+            result: match report.status {
+                ReportStatus::Passed if exit_code == 0 => TestResult::Passed,
+                _ => TestResult::Failed,
+            },
+            hint: report.hint.map(LocalizedText::resolve),
+            expected: report.expected,
+            actual: report.actual,
+            score: report.score,
+        },
+        None => TestCreate {
+            description: output.to_string(),
+            result: if exit_code == 0 {
+                TestResult::Passed
+            } else {
+                TestResult::Failed
+            },
+            hint: None,
+            expected: None,
+            actual: None,
+            score: None,
+        },
+    }
+}
+
+pub fn not_executed(description: String) -> TestCreate {
+    TestCreate {
+        description,
+        result: TestResult::NotExecuted,
+        hint: None,
+        expected: None,
+        actual: None,
+        score: None,
+    }
+}
+
+/// Result of the test number `number` that was killed after `timeout`,
+/// keeps whatever the test managed to print
+pub fn timed_out(number: usize, output: &str, timeout: Duration) -> TestCreate {
+    let mut description = tr_fmt(
+        "grading.timed_out",
+        &[("number", &number), ("secs", &timeout.as_secs())],
+    );
+    if !output.trim().is_empty() {
+        description = format!("{}\n{}", output.trim_end(), description);
+    }
+
+    TestCreate {
+        description,
+        result: TestResult::TimedOut,
+        hint: None,
+        expected: None,
+        actual: None,
+        score: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_output_uses_exit_code() {
+        let passed = parse_test_output("1. Репозиторий создан.\n", 0);
+        assert!(passed.result == TestResult::Passed);
+        assert_eq!(passed.description, "1. Репозиторий создан.\n");
+        assert!(passed.hint.is_none() && passed.score.is_none());
+
+        let failed = parse_test_output("1. Создайте репозиторий.", 1);
+        assert!(failed.result == TestResult::Failed);
+        assert_eq!(failed.description, "1. Создайте репозиторий.");
+    }
+
+    #[test]
+    fn report_line() {
+        let output = r#"git-trainer-report: {"status": "failed", "message": "3. Один коммит.", "hint": "git log", "expected": "1", "actual": "2", "score": 0.5}"#;
+        let test = parse_test_output(output, 1);
+        assert!(test.result == TestResult::Failed);
+        assert_eq!(test.description, "3. Один коммит.");
+        assert_eq!(test.hint.as_deref(), Some("git log"));
+        assert_eq!(test.expected.as_deref(), Some("1"));
+        assert_eq!(test.actual.as_deref(), Some("2"));
+        assert_eq!(test.score, Some(0.5));
+    }
+
+    #[test]
+    fn per_locale_texts() {
+        let output = format!(
+            r#"git-trainer-report: {{"status": "passed", "message": {{"{}": "current", "xx": "other"}}, "hint": {{"{}": "default"}}}}"#,
+            locale::current(),
+            DEFAULT_LOCALE
+        );
+        let test = parse_test_output(&output, 0);
+        assert!(test.result == TestResult::Passed);
+        assert_eq!(test.description, "current");
+        assert_eq!(test.hint.as_deref(), Some("default"));
+    }
+
+    #[test]
+    fn mixed_output_without_message() {
+        let output = "checking...\ngit-trainer-report: {\"status\": \"passed\"}\ndone";
+        let test = parse_test_output(output, 0);
+        assert!(test.result == TestResult::Passed);
+        assert_eq!(test.description, "checking...\ndone");
+    }
+
+    #[test]
+    fn bare_json_is_plain_text() {
+        // Например, тема коммита студента, которую тест вывел как есть
+        let output = "{\"status\": \"passed\"}";
+        let test = parse_test_output(output, 1);
+        assert!(test.result == TestResult::Failed);
+        assert_eq!(test.description, output);
+    }
+
+    #[test]
+    fn report_cannot_pass_failed_exit_code() {
+        let output = "git-trainer-report: {\"status\": \"passed\", \"message\": \"ok\"}";
+        let test = parse_test_output(output, 1);
+        assert!(test.result == TestResult::Failed);
+        assert_eq!(test.description, "ok");
+
+        let output = "git-trainer-report: {\"status\": \"failed\"}";
+        assert!(parse_test_output(output, 0).result == TestResult::Failed);
+    }
+}
@@ -0,0 +1,95 @@
+use chrono::{DateTime, Local};
+
+/// A command from .bash_history. Bash writes "#<unix time>" before every command
+/// when HISTTIMEFORMAT is set, older histories have no timestamps
+pub struct HistoryEntry {
+    pub timestamp: Option<DateTime<Local>>,
+    pub command: String,
+}
+
+fn parse_timestamp(line: &str) -> Option<DateTime<Local>> {
+    let secs = line.strip_prefix('#')?.parse::<i64>().ok()?;
+    DateTime::from_timestamp(secs, 0).map(|dt| dt.with_timezone(&Local))
+}
+
+pub fn parse_history(history: &str) -> Vec<HistoryEntry> {
+    let mut entries = Vec::new();
+    let mut timestamp = None;
+
+    for line in history.lines() {
+        if let Some(ts) = parse_timestamp(line) {
+            timestamp = Some(ts);
+            continue;
+        }
+        if line.trim().is_empty() {
+            continue;
+        }
+        entries.push(HistoryEntry {
+            timestamp: timestamp.take(),
+            command: line.to_string(),
+        });
+    }
+
+    entries
+}
+
+/// Returns lines of the history after the first `offset` lines and the new offset.
+/// A history shorter than the offset belongs to a recreated container and is taken whole.
+/// The base image disables truncation of .bash_history, otherwise the offset would drift
+pub fn history_since(history: &str, offset: usize) -> (String, usize) {
+    let lines: Vec<&str> = history.lines().collect();
+    let offset = if offset > lines.len() { 0 } else { offset };
+
+    let mut delta = lines[offset..].join("\n");
+    if !delta.is_empty() {
+        delta.push('\n');
+    }
+    (delta, lines.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HISTORY: &str = "#1700000000\ngit init\n#1700000060\ngit add .\n";
+
+    #[test]
+    fn from_the_start() {
+        assert_eq!(history_since(HISTORY, 0), (HISTORY.to_string(), 4));
+    }
+
+    #[test]
+    fn delta_keeps_timestamps_with_commands() {
+        let (delta, offset) = history_since(HISTORY, 2);
+        assert_eq!(delta, "#1700000060\ngit add .\n");
+        assert_eq!(offset, 4);
+
+        let entries = parse_history(&delta);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].command, "git add .");
+        assert_eq!(
+            entries[0].timestamp.map(|ts| ts.timestamp()),
+            Some(1700000060)
+        );
+    }
+
+    #[test]
+    fn nothing_new() {
+        assert_eq!(history_since(HISTORY, 4), (String::new(), 4));
+    }
+
+    #[test]
+    fn offset_past_the_end_takes_everything() {
+        // Контейнер пересоздан, и его история короче сохранённого смещения
+        let (delta, offset) = history_since("#1700000100\nls\n", 10);
+        assert_eq!(delta, "#1700000100\nls\n");
+        assert_eq!(offset, 2);
+    }
+
+    #[test]
+    fn history_without_timestamps() {
+        let entries = parse_history("git status\n\ngit log\n");
+        assert_eq!(entries.len(), 2);
+        assert!(entries.iter().all(|entry| entry.timestamp.is_none()));
+    }
+}
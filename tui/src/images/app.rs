@@ -0,0 +1,62 @@
+use crate::app::{App, AppStatus};
+use crate::images::{self, BuildOptions, BuildProgress};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::DefaultTerminal;
+use std::io;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+impl App {
+    /// Builds the base image and the images of all tasks, drawing the output as it comes
+    pub async fn build_images(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        self.image_build = Some(BuildProgress::default());
+        terminal.draw(|f| self.render_image_build(f))?;
+
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        let build = async {
+            let options = BuildOptions::from_config();
+            let targets = images::build_targets(&options.context, None)?;
+            images::build_images(targets, options, sender).await
+        };
+        tokio::pin!(build);
+
+        let result = loop {
+            tokio::select! {
+                result = &mut build => break result,
+                Some(event) = receiver.recv() => {
+                    if let Some(progress) = &mut self.image_build {
+                        progress.apply(event);
+                    }
+                }
+            }
+            terminal.draw(|f| self.render_image_build(f))?;
+        };
+
+        if let Some(progress) = &mut self.image_build {
+            while let Ok(event) = receiver.try_recv() {
+                progress.apply(event);
+            }
+            progress.current = None;
+            progress.result = Some(result.map_err(|e| e.to_string()));
+        }
+
+        // Клавиши, нажатые во время сборки, не должны закрыть экран с её результатом
+        while event::poll(Duration::ZERO)? {
+            event::read()?;
+        }
+        Ok(())
+    }
+
+    pub fn image_build_handle_events(&mut self) -> io::Result<()> {
+        if let Event::Key(key_event) = event::read()?
+            && key_event.kind == KeyEventKind::Press
+        {
+            match key_event.code {
+                KeyCode::Enter | KeyCode::Esc => self.status = AppStatus::RunningPreflight,
+                KeyCode::Char('q') => self.status = AppStatus::Exiting,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,520 @@
+pub mod app;
+pub mod ui;
+
+use crate::config::config;
+use crate::docker::{self, ImageBuild};
+use crate::locale::tr_fmt;
+use crate::manifest::{self, LoadManifestError};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tar::Builder;
+use thiserror::Error;
+use tokio::sync::mpsc::UnboundedSender;
+
+/// Directory of the base image inside `tasks`, it has a Dockerfile but no manifest
+const BASE_DIR: &str = "base";
+const BASE_TAG: &str = "base-task-image";
+/// Task Dockerfiles take the base image from this build argument
+const BASE_IMAGE_ARG: &str = "BASE_IMAGE";
+/// Content hash of everything the image was built from
+pub const CONTENT_HASH_LABEL: &str = "git-trainer.content-hash";
+/// Where the repository of the task is fetched to, relative to the task directory
+const SOURCE_DIR: &str = "src/repo";
+/// Length of the content hash in the extra tag of an image
+const SHORT_HASH_LEN: usize = 12;
+
+#[derive(Debug, Error)]
+#[allow(clippy::enum_variant_names)]
+pub enum BuildError {
+    #[error("While reading {0}: {1}")]
+    IoError(PathBuf, io::Error),
+
+    #[error("{0}")]
+    ManifestError(#[from] LoadManifestError),
+
+    #[error("There is no task \"{0}\" in {1}")]
+    UnknownTaskError(String, PathBuf),
+
+    #[error("While fetching the repository of \"{0}\": {1}")]
+    FetchError(String, String),
+
+    #[error("Preparation script of \"{0}\" failed: {1}")]
+    PrepareError(String, String),
+
+    #[error("While building {0}: {1}")]
+    DockerError(String, bollard::errors::Error),
+}
+
+/// One image to build. The base image goes first, task images are built from it
+pub struct BuildTarget {
+    /// work_name of the task, `base` for the base image
+    pub name: String,
+    pub image_name: String,
+    /// Relative to the build context, like the paths inside the Dockerfile
+    task_dir: PathBuf,
+    dockerfile: PathBuf,
+    source: Option<String>,
+    prepare: Option<String>,
+}
+
+impl BuildTarget {
+    fn is_base(&self) -> bool {
+        self.name == BASE_DIR
+    }
+
+    /// Whether the repository of the task is already in src/repo, if the task has one
+    fn is_fetched(&self, context: &Path) -> bool {
+        self.source.is_none() || context.join(&self.task_dir).join(SOURCE_DIR).exists()
+    }
+}
+
+pub struct BuildOptions {
+    pub context: PathBuf,
+    /// Directory with the task repositories by work_name, nothing is fetched from the network then
+    pub task_sources: Option<PathBuf>,
+    /// Rebuild the images that are up to date too
+    pub force: bool,
+}
+
+impl BuildOptions {
+    pub fn from_config() -> BuildOptions {
+        BuildOptions {
+            context: config().paths.build_context.clone(),
+            task_sources: config().paths.task_sources.clone(),
+            force: false,
+        }
+    }
+}
+
+pub enum BuildEvent {
+    Started(String),
+    /// A line of the git or Docker output
+    Output(String),
+    UpToDate(String),
+    /// The image and its short content hash
+    Built(String, String),
+}
+
+/// Output of a build in the TUI, the oldest lines are dropped after this many
+const MAX_LOG_EVENTS: usize = 2000;
+
+#[derive(Default)]
+pub struct BuildProgress {
+    pub log: Vec<BuildEvent>,
+    /// Image being built right now
+    pub current: Option<String>,
+    /// None while the build is running
+    pub result: Option<Result<(), String>>,
+}
+
+impl BuildProgress {
+    pub fn apply(&mut self, event: BuildEvent) {
+        if let BuildEvent::Started(image) = &event {
+            self.current = Some(image.clone());
+        }
+        self.log.push(event);
+        if self.log.len() > MAX_LOG_EVENTS {
+            self.log.drain(..self.log.len() - MAX_LOG_EVENTS);
+        }
+    }
+}
+
+pub fn base_image_name() -> String {
+    format!("{}:{}", config().docker.image_prefix, BASE_TAG)
+}
+
+/// The base image and the images of the tasks from `<context>/tasks`, only of `task` if it is given
+pub fn build_targets(context: &Path, task: Option<&str>) -> Result<Vec<BuildTarget>, BuildError> {
+    let tasks_dir = context.join("tasks");
+    // Без папки заданий load_manifests взял бы вшитые манифесты, а собирать их не из чего
+    if !tasks_dir.is_dir() {
+        return Err(BuildError::IoError(
+            tasks_dir,
+            io::ErrorKind::NotFound.into(),
+        ));
+    }
+    let manifests = manifest::load_manifests(&tasks_dir)?;
+    if let Some(task) = task
+        && !manifests.iter().any(|m| m.work_name == task)
+    {
+        return Err(BuildError::UnknownTaskError(task.to_string(), tasks_dir));
+    }
+
+    let base_dir = PathBuf::from("tasks").join(BASE_DIR);
+    let mut targets = vec![BuildTarget {
+        name: BASE_DIR.to_string(),
+        image_name: base_image_name(),
+        dockerfile: base_dir.join("src/Dockerfile"),
+        task_dir: base_dir,
+        source: None,
+        prepare: None,
+    }];
+    for manifest in manifests {
+        if task.is_some_and(|task| task != manifest.work_name) {
+            continue;
+        }
+        let task_dir = PathBuf::from("tasks").join(&manifest.work_name);
+        targets.push(BuildTarget {
+            image_name: manifest.image_name(),
+            dockerfile: task_dir.join(&manifest.build.dockerfile),
+            task_dir,
+            name: manifest.work_name,
+            source: manifest.build.source,
+            prepare: manifest.build.prepare,
+        });
+    }
+    Ok(targets)
+}
+
+/// Files and directories the Dockerfile copies from the context, `COPY --from` excluded
+fn copied_paths(dockerfile: &str) -> Vec<String> {
+    let mut paths = Vec::new();
+    for instruction in dockerfile.replace("\\\n", " ").lines() {
+        let mut words = instruction.split_whitespace();
+        let Some(command) = words.next() else {
+            continue;
+        };
+        if !command.eq_ignore_ascii_case("COPY") && !command.eq_ignore_ascii_case("ADD") {
+            continue;
+        }
+        let words: Vec<&str> = words.collect();
+        if words.iter().any(|word| word.starts_with("--from")) {
+            continue;
+        }
+        let args: Vec<&str> = words
+            .into_iter()
+            .filter(|word| !word.starts_with("--"))
+            .collect();
+        // Последний аргумент — путь внутри образа
+        if let Some((_, sources)) = args.split_last() {
+            paths.extend(
+                sources
+                    .iter()
+                    .map(|source| source.trim_end_matches('/').to_string()),
+            );
+        }
+    }
+    paths
+}
+
+/// A directory with HEAD and objects, a .git or a bare repository
+fn is_git_dir(path: &Path) -> bool {
+    path.join("HEAD").is_file() && path.join("objects").is_dir()
+}
+
+/// Git repositories are hashed by their refs, their object files differ from clone to clone
+fn hash_path(hasher: &mut Sha256, context: &Path, relative: &Path) -> io::Result<()> {
+    let path = context.join(relative);
+    let metadata = fs::symlink_metadata(&path)?;
+    hasher.update(relative.to_string_lossy().as_bytes());
+    hasher.update([0]);
+
+    if metadata.is_symlink() {
+        hasher.update(fs::read_link(&path)?.to_string_lossy().as_bytes());
+    } else if metadata.is_dir() && is_git_dir(&path) {
+        let refs = Command::new("git")
+            .arg("--git-dir")
+            .arg(&path)
+            .args(["show-ref", "--head"])
+            .output()?;
+        hasher.update(&refs.stdout);
+    } else if metadata.is_dir() {
+        let mut entries: Vec<PathBuf> = fs::read_dir(&path)?
+            .map(|entry| entry.map(|entry| relative.join(entry.file_name())))
+            .collect::<io::Result<_>>()?;
+        entries.sort();
+        for entry in entries {
+            hash_path(hasher, context, &entry)?;
+        }
+    } else {
+        hasher.update(fs::read(&path)?);
+    }
+    hasher.update([0]);
+    Ok(())
+}
+
+/// Hash of the Dockerfile, of everything it copies and of the base image hash.
+/// Images are labeled with it, so an image is stale when the hash of its sources changes
+fn content_hash(
+    context: &Path,
+    target: &BuildTarget,
+    base_hash: Option<&str>,
+) -> Result<String, BuildError> {
+    let dockerfile_path = context.join(&target.dockerfile);
+    let dockerfile = fs::read_to_string(&dockerfile_path)
+        .map_err(|e| BuildError::IoError(dockerfile_path.clone(), e))?;
+
+    let mut hasher = Sha256::new();
+    hasher.update(dockerfile.as_bytes());
+    hasher.update([0]);
+    for path in copied_paths(&dockerfile) {
+        hash_path(&mut hasher, context, Path::new(&path))
+            .map_err(|e| BuildError::IoError(context.join(&path), e))?;
+    }
+    if let Some(base_hash) = base_hash {
+        hasher.update(base_hash.as_bytes());
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Hashes of the images as they would be built from the context right now, by target name.
+/// Targets whose sources are not fetched yet are left out
+pub fn expected_hashes(context: &Path, targets: &[BuildTarget]) -> HashMap<String, String> {
+    let mut hashes = HashMap::new();
+    let Some(base_hash) = targets
+        .iter()
+        .find(|target| target.is_base())
+        .and_then(|base| content_hash(context, base, None).ok())
+    else {
+        return hashes;
+    };
+
+    for target in targets.iter().filter(|target| !target.is_base()) {
+        if !target.is_fetched(context) {
+            continue;
+        }
+        if let Ok(hash) = content_hash(context, target, Some(&base_hash)) {
+            hashes.insert(target.name.clone(), hash);
+        }
+    }
+    hashes.insert(BASE_DIR.to_string(), base_hash);
+    hashes
+}
+
+fn copy_dir(from: &Path, to: &Path) -> io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let (from, to) = (entry.path(), to.join(entry.file_name()));
+        let file_type = entry.file_type()?;
+        if file_type.is_symlink() {
+            std::os::unix::fs::symlink(fs::read_link(&from)?, &to)?;
+        } else if file_type.is_dir() {
+            copy_dir(&from, &to)?;
+        } else {
+            fs::copy(&from, &to)?;
+        }
+    }
+    Ok(())
+}
+
+async fn run_command(command: &mut tokio::process::Command) -> Result<(), String> {
+    let output = command.output().await.map_err(|e| e.to_string())?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+/// Fetches the repository of the task into src/repo, from `task_sources` if it is given,
+/// and runs the preparation script
+async fn fetch_source(
+    target: &BuildTarget,
+    options: &BuildOptions,
+    events: &UnboundedSender<BuildEvent>,
+) -> Result<(), BuildError> {
+    let task_dir = options.context.join(&target.task_dir);
+    if let Some(source) = &target.source {
+        let repo_dir = task_dir.join(SOURCE_DIR);
+        if repo_dir.exists() {
+            fs::remove_dir_all(&repo_dir).map_err(|e| BuildError::IoError(repo_dir.clone(), e))?;
+        }
+
+        match &options.task_sources {
+            Some(task_sources) => {
+                let local = task_sources.join(&target.name);
+                let _ = events.send(BuildEvent::Output(tr_fmt(
+                    "images.copying",
+                    &[("path", &local.display())],
+                )));
+                copy_dir(&local, &repo_dir).map_err(|e| BuildError::IoError(local, e))?;
+            }
+            None => {
+                let _ = events.send(BuildEvent::Output(tr_fmt(
+                    "images.cloning",
+                    &[("url", source)],
+                )));
+                run_command(
+                    tokio::process::Command::new("git")
+                        .args(["clone", "--quiet", source])
+                        .arg(&repo_dir),
+                )
+                .await
+                .map_err(|e| BuildError::FetchError(target.name.clone(), e))?;
+            }
+        }
+    }
+
+    if let Some(prepare) = &target.prepare {
+        let _ = events.send(BuildEvent::Output(tr_fmt(
+            "images.preparing",
+            &[("script", prepare)],
+        )));
+        run_command(tokio::process::Command::new(task_dir.join(prepare)).current_dir(&task_dir))
+            .await
+            .map_err(|e| BuildError::PrepareError(target.name.clone(), e))?;
+    }
+    Ok(())
+}
+
+/// Tar of the Dockerfile and of everything it copies, the rest of the context is not sent
+fn build_context(context: &Path, target: &BuildTarget) -> io::Result<Vec<u8>> {
+    let dockerfile = fs::read_to_string(context.join(&target.dockerfile))?;
+    let mut tar_data = Vec::new();
+    {
+        let mut builder = Builder::new(&mut tar_data);
+        builder.follow_symlinks(false);
+        builder.append_path_with_name(context.join(&target.dockerfile), &target.dockerfile)?;
+        for path in copied_paths(&dockerfile) {
+            let full_path = context.join(&path);
+            if full_path.is_dir() {
+                builder.append_dir_all(&path, &full_path)?;
+            } else {
+                builder.append_path_with_name(&full_path, &path)?;
+            }
+        }
+        builder.finish()?;
+    }
+    Ok(tar_data)
+}
+
+async fn build_target(
+    target: &BuildTarget,
+    hash: &str,
+    options: &BuildOptions,
+    events: &UnboundedSender<BuildEvent>,
+) -> Result<(), BuildError> {
+    let docker_error = |e| BuildError::DockerError(target.image_name.clone(), e);
+    let context = build_context(&options.context, target)
+        .map_err(|e| BuildError::IoError(options.context.join(&target.task_dir), e))?;
+    let dockerfile = target.dockerfile.to_string_lossy();
+
+    let build = ImageBuild {
+        context,
+        dockerfile: &dockerfile,
+        image_name: &target.image_name,
+        build_args: HashMap::from([(BASE_IMAGE_ARG.to_string(), base_image_name())]),
+        labels: HashMap::from([(CONTENT_HASH_LABEL.to_string(), hash.to_string())]),
+    };
+    docker::build_image(build, |line| {
+        let _ = events.send(BuildEvent::Output(line));
+    })
+    .await
+    .map_err(docker_error)?;
+
+    // Второй тег с хешем показывает, из каких исходников собран образ
+    let (repo, tag) = target
+        .image_name
+        .rsplit_once(':')
+        .unwrap_or((&target.image_name, "latest"));
+    let short_hash = &hash[..SHORT_HASH_LEN];
+    docker::tag_image(&target.image_name, repo, &format!("{}-{}", tag, short_hash))
+        .await
+        .map_err(docker_error)?;
+    Ok(())
+}
+
+/// Builds the targets in order, skipping images whose content hash has not changed.
+/// The repository of a task is fetched again only if its image is stale or `force` is set,
+/// so up-to-date images are checked without the network. Progress is reported through `events`
+pub async fn build_images(
+    targets: Vec<BuildTarget>,
+    options: BuildOptions,
+    events: UnboundedSender<BuildEvent>,
+) -> Result<(), BuildError> {
+    let mut base_hash = None;
+    for target in &targets {
+        let _ = events.send(BuildEvent::Started(target.image_name.clone()));
+        let current = docker::image_label(&target.image_name, CONTENT_HASH_LABEL)
+            .await
+            .map_err(|e| BuildError::DockerError(target.image_name.clone(), e))?;
+        let is_current = |hash: &str| !options.force && current.as_deref() == Some(hash);
+
+        let hash_now = || content_hash(&options.context, target, base_hash.as_deref());
+        let hash = match target
+            .is_fetched(&options.context)
+            .then(hash_now)
+            .transpose()?
+        {
+            Some(hash) if is_current(&hash) => hash,
+            _ => {
+                fetch_source(target, &options, &events).await?;
+                hash_now()?
+            }
+        };
+
+        if is_current(&hash) {
+            let _ = events.send(BuildEvent::UpToDate(target.image_name.clone()));
+        } else {
+            build_target(target, &hash, &options, &events).await?;
+            let _ = events.send(BuildEvent::Built(
+                target.image_name.clone(),
+                hash[..SHORT_HASH_LEN].to_string(),
+            ));
+        }
+
+        if target.is_base() {
+            base_hash = Some(hash);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn copied_paths_of_dockerfile() {
+        let dockerfile = "FROM $BASE_IMAGE\n\
+                          COPY --chown=student:student tasks/branching/src/repo counting-sort\n\
+                          COPY --from=builder /build/cli /usr/local/bin/git-trainer\n\
+                          ADD a.txt \\\n    b/ /dest/\n\
+                          RUN cp a b\n";
+        assert_eq!(
+            copied_paths(dockerfile),
+            ["tasks/branching/src/repo", "a.txt", "b"]
+        );
+    }
+
+    #[test]
+    fn base_image_does_not_depend_on_tui_sources() {
+        let dockerfile = include_str!("../../../tasks/base/src/Dockerfile");
+        assert_eq!(
+            copied_paths(dockerfile),
+            [
+                "Cargo.toml",
+                "Cargo.lock",
+                "tui/Cargo.toml",
+                "cli",
+                "locales"
+            ]
+        );
+    }
+
+    #[test]
+    fn task_images_do_not_depend_on_trainer_sources() {
+        let tasks = Path::new(env!("CARGO_MANIFEST_DIR")).join("../tasks");
+        for entry in fs::read_dir(&tasks).unwrap() {
+            let task_dir = entry.unwrap().path();
+            let dockerfile = task_dir.join("src/Dockerfile");
+            // git-trainer собирается только в базовом образе, задания лишь наследуют его
+            if task_dir.ends_with("base") || !dockerfile.is_file() {
+                continue;
+            }
+            for path in copied_paths(&fs::read_to_string(&dockerfile).unwrap()) {
+                assert!(
+                    path.starts_with("tasks/"),
+                    "{} copies {}",
+                    dockerfile.display(),
+                    path
+                );
+            }
+        }
+    }
+}
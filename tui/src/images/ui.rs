@@ -0,0 +1,80 @@
+use crate::Frame;
+use crate::app::{App, VERSION};
+use crate::images::BuildEvent;
+use crate::locale::{tr, tr_fmt};
+use ratatui::layout::{Alignment, Constraint, Direction, Layout};
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Paragraph};
+
+fn event_line(event: &BuildEvent) -> Line<'static> {
+    match event {
+        BuildEvent::Started(image) => Line::from(format!("==> {}", image).bold()),
+        BuildEvent::Output(line) => Line::from(line.clone()),
+        BuildEvent::UpToDate(image) => Line::from(
+            format!("✔ {}", tr_fmt("images.up_to_date", &[("image", image)])).light_green(),
+        ),
+        BuildEvent::Built(image, hash) => Line::from(
+            format!(
+                "✔ {}",
+                tr_fmt("images.built", &[("image", image), ("hash", hash)])
+            )
+            .light_green(),
+        ),
+    }
+}
+
+impl App {
+    pub fn render_image_build(&self, frame: &mut Frame) {
+        let Some(progress) = &self.image_build else {
+            return;
+        };
+        let title = Line::from(format!("git-trainer v{}", VERSION).bold()).centered();
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1),
+                Constraint::Min(1),
+                Constraint::Length(1),
+                Constraint::Length(1),
+            ])
+            .split(frame.area());
+
+        // Видны только последние строки вывода, которые помещаются в окно
+        let visible = chunks[1].height.saturating_sub(2) as usize;
+        let lines: Vec<Line> = progress
+            .log
+            .iter()
+            .skip(progress.log.len().saturating_sub(visible))
+            .map(event_line)
+            .collect();
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(tr("images.title").bold());
+        let log = Paragraph::new(lines).block(block);
+
+        let status = match (&progress.result, &progress.current) {
+            (Some(Ok(())), _) => Line::from(format!("✔ {}", tr("images.done")).light_green()),
+            (Some(Err(error)), _) => Line::from(
+                format!("✘ {}", tr_fmt("images.failed", &[("error", error)])).light_red(),
+            ),
+            (None, Some(image)) => {
+                Line::from(tr_fmt("images.building", &[("image", image)]).yellow())
+            }
+            (None, None) => Line::from(tr("images.starting").dark_gray()),
+        };
+        let footer = if progress.result.is_some() {
+            tr("images.footer")
+        } else {
+            tr("images.running_footer")
+        };
+
+        frame.render_widget(title, chunks[0]);
+        frame.render_widget(log, chunks[1]);
+        frame.render_widget(status.centered(), chunks[2]);
+        frame.render_widget(
+            Paragraph::new(footer).alignment(Alignment::Center),
+            chunks[3],
+        );
+    }
+}
@@ -0,0 +1,158 @@
+use crate::config::{Config, config};
+use crate::embedded;
+use std::collections::HashMap;
+use std::env;
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use thiserror::Error;
+
+/// Messages missing from the selected catalog are taken from this one
+pub const DEFAULT_LOCALE: &str = "ru";
+/// Passes the locale to task containers, so that the CLI and the tests speak the same language
+pub const LOCALE_ENV: &str = "GIT_TRAINER_LOCALE";
+
+static CATALOG: OnceLock<Catalog> = OnceLock::new();
+
+#[derive(Debug, Error)]
+pub enum LocaleError {
+    #[error("While reading {0}: {1}")]
+    IoError(PathBuf, io::Error),
+
+    #[error("While parsing {0}: {1}")]
+    ParseError(PathBuf, toml::de::Error),
+}
+
+struct Catalog {
+    locale: String,
+    /// Messages by dotted keys, e.g. `popup.run_title`
+    messages: HashMap<String, String>,
+}
+
+fn flatten(prefix: &str, table: toml::Table, messages: &mut HashMap<String, String>) {
+    for (key, value) in table {
+        let key = if prefix.is_empty() {
+            key
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        match value {
+            toml::Value::Table(table) => flatten(&key, table, messages),
+            toml::Value::String(message) => {
+                messages.insert(key, message);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The built-in catalog of the locale with `<locale>.toml` from `paths.locales` over it.
+/// None if the locale has neither
+fn load_messages(
+    locale: &str,
+    locales_dir: &Path,
+) -> Result<Option<HashMap<String, String>>, LocaleError> {
+    let mut messages = HashMap::new();
+    let mut found = false;
+
+    if let Some((_, content)) = embedded::LOCALES.iter().find(|(name, _)| *name == locale)
+        && let Ok(table) = toml::from_str(content)
+    {
+        flatten("", table, &mut messages);
+        found = true;
+    }
+
+    let path = locales_dir.join(format!("{}.toml", locale));
+    match fs::read_to_string(&path) {
+        Ok(content) => {
+            let table = toml::from_str(&content).map_err(|e| LocaleError::ParseError(path, e))?;
+            flatten("", table, &mut messages);
+            found = true;
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+        Err(e) => return Err(LocaleError::IoError(path, e)),
+    }
+
+    Ok(found.then_some(messages))
+}
+
+/// `ui.locale` from the config, otherwise the language of `LC_ALL`, `LC_MESSAGES` or `LANG`
+fn requested_locale(config: &Config) -> Option<String> {
+    if let Some(locale) = &config.ui.locale {
+        return Some(locale.clone());
+    }
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .into_iter()
+        .filter_map(|name| env::var(name).ok())
+        .find(|value| !value.is_empty())
+        .map(|value| {
+            // en_US.UTF-8 → en
+            value
+                .split(['_', '.', '@'])
+                .next()
+                .unwrap_or_default()
+                .to_lowercase()
+        })
+        .filter(|language| !language.is_empty() && language != "c" && language != "posix")
+}
+
+fn load_catalog(config: &Config) -> Result<Catalog, LocaleError> {
+    let locales_dir = &config.paths.locales;
+    let requested = requested_locale(config);
+    let mut catalog = Catalog {
+        locale: DEFAULT_LOCALE.to_string(),
+        messages: HashMap::new(),
+    };
+    if let Some(locale) = requested
+        && let Some(messages) = load_messages(&locale, locales_dir)?
+    {
+        catalog = Catalog { locale, messages };
+    }
+
+    if catalog.locale != DEFAULT_LOCALE {
+        for (key, message) in load_messages(DEFAULT_LOCALE, locales_dir)?.unwrap_or_default() {
+            catalog.messages.entry(key).or_insert(message);
+        }
+    } else {
+        catalog.messages = load_messages(DEFAULT_LOCALE, locales_dir)?.unwrap_or_default();
+    }
+    Ok(catalog)
+}
+
+/// Selects the locale and loads its catalog. It is done before `config::init`,
+/// so that the command line help is already localized
+pub fn init(config: &Config) -> Result<(), LocaleError> {
+    let catalog = load_catalog(config)?;
+    let _ = CATALOG.set(catalog);
+    Ok(())
+}
+
+fn catalog() -> &'static Catalog {
+    CATALOG.get_or_init(|| {
+        load_catalog(config()).unwrap_or_else(|_| Catalog {
+            locale: DEFAULT_LOCALE.to_string(),
+            messages: HashMap::new(),
+        })
+    })
+}
+
+/// Locale of the interface, one of the catalogs that exist
+pub fn current() -> &'static str {
+    &catalog().locale
+}
+
+/// Message by its key, the key itself if no catalog has it
+pub fn tr(key: &str) -> &str {
+    catalog().messages.get(key).map_or(key, String::as_str)
+}
+
+/// Message by its key with `{name}` placeholders replaced by the values
+pub fn tr_fmt(key: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut message = tr(key).to_string();
+    for (name, value) in args {
+        message = message.replace(&format!("{{{}}}", name), &value.to_string());
+    }
+    message
+}
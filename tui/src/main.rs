@@ -1,26 +1,106 @@
+mod admin;
 mod app;
 mod attempt_manager;
+mod config;
 mod db;
 mod docker;
+mod embedded;
+mod error;
+mod events;
+mod grading;
+mod history;
+mod images;
+mod locale;
 mod main_menu;
+mod manifest;
+mod migrations;
+mod player;
 mod popup;
+mod preflight;
 mod pty;
-use crate::app::{App, AppStatus};
-use ratatui::Frame;
+mod recording;
+use crate::app::{App, AppStatus, VERSION};
+use crate::error::AppError;
+use crate::popup::Popup;
+use crossterm::event::{self, Event, KeyEventKind};
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::{DefaultTerminal, Frame};
 use std::io;
 
-async fn run() -> bool {
-    let mut terminal = ratatui::init();
-    let _ = color_eyre::install();
+async fn run(terminal: &mut DefaultTerminal) -> Result<bool, AppError> {
+    let mut app = App::new()?;
+    app.run_app(terminal).await?;
+
+    Ok(!matches!(app.status, AppStatus::Exiting))
+}
 
-    let mut app = App::new();
-    let _ = app.run_app(&mut terminal).await;
+/// Shows the error that stopped git-trainer until any key is pressed
+fn show_error(terminal: &mut DefaultTerminal, error: &AppError) -> io::Result<()> {
+    let popup = Popup::Error(error.to_string());
+    loop {
+        terminal.draw(|frame| {
+            let title = Line::from(format!("git-trainer v{}", VERSION).bold()).centered();
+            frame.render_widget(title, frame.area());
+            popup.render(frame);
+        })?;
+        if let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+        {
+            return Ok(());
+        }
+    }
+}
 
-    !matches!(app.status, AppStatus::Exiting)
+/// The config and the locale are loaded before the terminal is taken over,
+/// so their errors are simply printed
+fn load_config() -> Result<config::Config, AppError> {
+    let config = config::Config::load()?;
+    locale::init(&config)?;
+    Ok(config)
 }
 
 #[tokio::main]
 async fn main() {
-    while run().await {}
+    let mut config = load_config().unwrap_or_else(|err| {
+        eprintln!("{err}");
+        std::process::exit(1);
+    });
+
+    let mut args = admin::parse_args();
+    if args.record {
+        config.ui.record_sessions = true;
+    }
+    if let Some(scrollback) = args.scrollback {
+        config.ui.scrollback = scrollback;
+    }
+    config::init(config);
+
+    if let Some(command) = args.command.take() {
+        if let Err(err) = admin::run(command).await {
+            eprintln!("{err}");
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    // Хук паники ratatui восстанавливает терминал и затем вызывает предыдущий хук,
+    // поэтому color_eyre должен быть установлен раньше
+    let _ = color_eyre::install();
+    let mut terminal = ratatui::init();
+    let result = loop {
+        match run(&mut terminal).await {
+            Ok(true) => continue,
+            Ok(false) => break Ok(()),
+            Err(err) => break Err(err),
+        }
+    };
+    if let Err(err) = &result {
+        let _ = show_error(&mut terminal, err);
+    }
     ratatui::restore();
+
+    if result.is_err() {
+        std::process::exit(1);
+    }
 }
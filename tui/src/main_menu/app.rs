@@ -31,6 +31,11 @@ impl App {
                             self.status = AppStatus::RestartingTask;
                             self.active_popup = Some(Popup::ResetDone);
                         }
+                        Popup::ContainerOutdated => {
+                            self.status = AppStatus::RecreatingContainer {
+                                keep_repository: true,
+                            };
+                        }
                         _ => self.active_popup = None,
                     }
                 } else {
@@ -40,6 +45,21 @@ impl App {
             KeyCode::Char('r') => {
                 self.active_popup = Some(Popup::ResetConfirmation);
             }
+            KeyCode::Char('d') if matches!(self.active_popup, Some(Popup::ContainerOutdated)) => {
+                self.active_popup = None;
+                self.status = AppStatus::RecreatingContainer {
+                    keep_repository: false,
+                };
+            }
+            KeyCode::Char('o') if matches!(self.active_popup, Some(Popup::ContainerOutdated)) => {
+                self.active_popup = None;
+                let container_name = self.task_under_cursor().container_name.clone();
+                self.kept_outdated_containers.insert(container_name);
+                self.status = AppStatus::RunningTask;
+            }
+            KeyCode::Char('c') if !self.is_popup_active() => {
+                self.status = AppStatus::RunningPreflight;
+            }
 
             KeyCode::Esc => {
                 self.active_popup = None;
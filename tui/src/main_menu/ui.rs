@@ -1,11 +1,14 @@
 use crate::app::App;
 use crate::app::VERSION;
 use crate::db::TaskStatus;
+use crate::locale::tr;
+use crate::preflight::TaskReadiness;
+use crate::preflight::ui::readiness_span;
 use ratatui::Frame;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::{Color, Modifier, Style, Stylize, palette::tailwind};
 use ratatui::text::{Line, Text};
-use ratatui::widgets::{Cell, Paragraph, Row, Table};
+use ratatui::widgets::{Block, Borders, Cell, Paragraph, Row, Table, Wrap};
 
 const LINE_WIDTH: u16 = 50;
 
@@ -104,7 +107,7 @@ impl TableColors {
 
 fn get_max_task_name_length(app: &App) -> usize {
     let mut max = usize::MIN;
-    for task in app.repo.get_all_tasks().expect("While working with db:") {
+    for task in &app.context.tasks {
         if task.name.chars().count() > max {
             max = task.name.chars().count();
         }
@@ -125,34 +128,77 @@ impl App {
             ])
             .split(frame.area());
 
-        let how_to_use_string =
-            "← ↑ ↓ → — перемещение, q — выход, Enter — начать задание, r — перезагрузить задание, Пробел — открыть менеджер попыток"
-                .to_string();
-        let how_to_use = Paragraph::new(how_to_use_string).centered();
+        let how_to_use = Paragraph::new(tr("main_menu.footer")).centered();
 
         frame.render_widget(how_to_use, global_layout[2]);
         frame.render_widget(title, global_layout[0]);
 
-        self.render_table(frame, global_layout[1]);
+        let [table_area, details_area] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Percentage(35)])
+                .areas(global_layout[1]);
+        self.render_table(frame, table_area);
+        self.render_task_details(frame, details_area);
         if let Some(popup) = &self.active_popup {
             popup.render(frame);
         }
     }
 
+    /// Full statement and metadata of the task under cursor
+    fn render_task_details(&self, frame: &mut Frame, rect: Rect) {
+        let task = self.task_under_cursor();
+        let mut lines: Vec<Line> = task
+            .extended_description
+            .lines()
+            .map(|line| Line::from(line.to_string()))
+            .collect();
+        lines.push(Line::default());
+
+        let mut details = Vec::new();
+        if let Some(difficulty) = &task.difficulty {
+            details.push((tr("main_menu.difficulty"), difficulty.clone()));
+        }
+        if let Some(author) = &task.author {
+            details.push((tr("main_menu.author"), author.clone()));
+        }
+        if !task.tags.is_empty() {
+            details.push((tr("main_menu.tags"), task.tags.join(", ")));
+        }
+        if let Some(manifest) = self.manifest_of(task) {
+            details.push((tr("main_menu.tests"), manifest.tests.len().to_string()));
+            details.push((tr("main_menu.hints"), manifest.hints.len().to_string()));
+        }
+        details.push((tr("main_menu.attempts"), task.attempts.len().to_string()));
+        for (label, value) in details {
+            lines.push(Line::from(vec![
+                format!("{}: ", label).bold(),
+                value.into(),
+            ]));
+        }
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(task.name.as_str().bold());
+        let paragraph = Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap { trim: false });
+        frame.render_widget(paragraph, rect);
+    }
+
     fn render_table(&mut self, frame: &mut Frame, rect: Rect) {
         let max_task_name_length = get_max_task_name_length(self) as u16;
         let colors = TableColors::new();
 
-        let header = ["Название", "Описание", "Статус"]
-            .into_iter()
-            .map(Cell::from)
-            .collect::<Row>()
-            .height(1);
-        let binding = self
-            .repo
-            .get_tasks_user_local(self.context.user.as_ref().unwrap().id)
-            .expect("While working with db:");
-        let rows = binding.iter().enumerate().map(|(i, data)| {
+        let header = [
+            tr("main_menu.column_name"),
+            tr("main_menu.column_description"),
+            tr("main_menu.column_status"),
+            tr("main_menu.column_readiness"),
+        ]
+        .into_iter()
+        .map(Cell::from)
+        .collect::<Row>()
+        .height(1);
+        let rows = self.context.tasks.iter().enumerate().map(|(i, data)| {
             let row_bg = if i % 2 == 0 {
                 colors.normal_row_color
             } else {
@@ -182,6 +228,14 @@ impl App {
 
                 cell
             });
+            let readiness = self.preflight.as_ref().map(|report| {
+                report
+                    .task(&data.work_name)
+                    .map_or(TaskReadiness::Unknown, |checks| checks.readiness())
+            });
+            let readiness_cell =
+                Cell::from(readiness_span(readiness)).style(Style::new().bg(row_bg));
+            let cells = cells.chain(std::iter::once(readiness_cell));
 
             Row::new(cells).height(4).style(Style::new().bg(row_bg))
         });
@@ -196,6 +250,7 @@ impl App {
                 Constraint::Length(max_task_name_length + 6),
                 Constraint::Min(LINE_WIDTH),
                 Constraint::Min(10),
+                Constraint::Min(14),
             ],
         )
         .header(header)
@@ -0,0 +1,263 @@
+use crate::config::config;
+use crate::embedded;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Name of the manifest file inside every task directory
+pub const MANIFEST_FILE_NAME: &str = "task.toml";
+
+/// Home directory of the student inside task containers
+pub const STUDENT_HOME: &str = "/home/student";
+
+/// TaskManifest is the single source of truth for a task, loaded from tasks/<work_name>/task.toml
+#[derive(Deserialize, Clone)]
+pub struct TaskManifest {
+    pub name: String,
+    pub work_name: String,
+    pub description: String,
+    pub extended_description: String,
+    pub image: Option<String>,
+    /// Directory of the task repository relative to the student's home, the home itself if not set
+    pub repository: Option<String>,
+    #[serde(default)]
+    pub tests: Vec<TestManifest>,
+    #[serde(default)]
+    pub grading: GradingManifest,
+    /// Hints are revealed one by one in the declaration order
+    #[serde(default)]
+    pub hints: Vec<HintManifest>,
+    #[serde(default)]
+    pub metadata: TaskMetadata,
+    #[serde(default)]
+    pub build: BuildManifest,
+    /// Texts of the task in other languages by locale, e.g. `[locales.en]`
+    #[serde(default)]
+    pub locales: HashMap<String, LocalizedTexts>,
+}
+
+/// TestManifest describes a single test script from tests/<work_name>
+#[derive(Deserialize, Clone)]
+pub struct TestManifest {
+    pub script: String,
+    /// Overrides grading.test_timeout_secs for this test
+    pub timeout_secs: Option<u64>,
+    /// Scripts that must pass before this test is run
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct HintManifest {
+    pub text: String,
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(default)]
+pub struct GradingManifest {
+    /// How long a single test may run before it is killed
+    pub test_timeout_secs: u64,
+    /// How long the whole attempt may be graded, tests that did not fit are not executed
+    pub attempt_timeout_secs: u64,
+}
+
+impl Default for GradingManifest {
+    fn default() -> Self {
+        GradingManifest {
+            test_timeout_secs: 30,
+            attempt_timeout_secs: 300,
+        }
+    }
+}
+
+/// Replaces the texts of the task for one locale, the ones not given stay as they are
+#[derive(Deserialize, Clone, Default)]
+pub struct LocalizedTexts {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub extended_description: Option<String>,
+    /// Replaces all hints at once, so that their order and count stay consistent
+    #[serde(default)]
+    pub hints: Vec<HintManifest>,
+}
+
+/// How `git-trainer build` builds the image of the task
+#[derive(Deserialize, Clone)]
+#[serde(default)]
+pub struct BuildManifest {
+    /// Relative to the task directory
+    pub dockerfile: String,
+    /// Git repository of the task, fetched into src/repo before the build
+    pub source: Option<String>,
+    /// Script in the task directory run after the repository is fetched, from that directory
+    pub prepare: Option<String>,
+}
+
+impl Default for BuildManifest {
+    fn default() -> Self {
+        BuildManifest {
+            dockerfile: "src/Dockerfile".to_string(),
+            source: None,
+            prepare: None,
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Default)]
+pub struct TaskMetadata {
+    /// Position of the task in the main menu for new databases
+    pub order: Option<i64>,
+    pub author: Option<String>,
+    pub difficulty: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+impl TaskManifest {
+    pub fn image_name(&self) -> String {
+        match &self.image {
+            Some(image) => image.clone(),
+            None => default_image_name(&self.work_name),
+        }
+    }
+
+    /// Applies the texts of the locale, if the manifest has them
+    pub fn localize(&mut self, locale: &str) {
+        let Some(texts) = self.locales.get(locale).cloned() else {
+            return;
+        };
+        if let Some(name) = texts.name {
+            self.name = name;
+        }
+        if let Some(description) = texts.description {
+            self.description = description;
+        }
+        if let Some(extended_description) = texts.extended_description {
+            self.extended_description = extended_description;
+        }
+        if !texts.hints.is_empty() {
+            self.hints = texts.hints;
+        }
+    }
+
+    pub fn repository_path(&self) -> String {
+        match &self.repository {
+            Some(repository) => format!("{}/{}", STUDENT_HOME, repository),
+            None => STUDENT_HOME.to_string(),
+        }
+    }
+
+    /// Indices of tests in the order they should be run: every test goes after
+    /// the tests it depends on, otherwise the declaration order is kept
+    pub fn test_execution_order(&self) -> Result<Vec<usize>, LoadManifestError> {
+        let mut dependencies = Vec::new();
+        for test in &self.tests {
+            let mut indices = Vec::new();
+            for dependency in &test.depends_on {
+                let index = self
+                    .tests
+                    .iter()
+                    .position(|t| &t.script == dependency)
+                    .ok_or_else(|| {
+                        LoadManifestError::UnknownTestDependency(
+                            self.work_name.clone(),
+                            test.script.clone(),
+                            dependency.clone(),
+                        )
+                    })?;
+                indices.push(index);
+            }
+            dependencies.push(indices);
+        }
+
+        let mut order = Vec::new();
+        let mut done = vec![false; self.tests.len()];
+        while order.len() < self.tests.len() {
+            let next = (0..self.tests.len())
+                .find(|&i| !done[i] && dependencies[i].iter().all(|&d| done[d]))
+                .ok_or_else(|| LoadManifestError::TestDependencyCycle(self.work_name.clone()))?;
+            done[next] = true;
+            order.push(next);
+        }
+        Ok(order)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum LoadManifestError {
+    #[error("While reading {0}: {1}")]
+    IoError(PathBuf, std::io::Error),
+
+    #[error("While parsing {0}: {1}")]
+    ParseError(PathBuf, toml::de::Error),
+
+    #[error("While parsing embedded manifest: {0}")]
+    EmbeddedParseError(toml::de::Error),
+
+    #[error("Task work_name \"{0}\" is declared more than once")]
+    DuplicateWorkName(String),
+
+    #[error("Test {1} of task \"{0}\" depends on unknown test {2}")]
+    UnknownTestDependency(String, String, String),
+
+    #[error("Tests of task \"{0}\" have cyclic dependencies")]
+    TestDependencyCycle(String),
+}
+
+pub fn tasks_dir() -> &'static Path {
+    &config().paths.tasks
+}
+
+pub fn default_image_name(work_name: &str) -> String {
+    format!("{}:{}", config().docker.image_prefix, work_name)
+}
+
+pub fn load_manifest(path: &Path) -> Result<TaskManifest, LoadManifestError> {
+    let content =
+        fs::read_to_string(path).map_err(|e| LoadManifestError::IoError(path.to_path_buf(), e))?;
+    toml::from_str(&content).map_err(|e| LoadManifestError::ParseError(path.to_path_buf(), e))
+}
+
+/// Loads every tasks/*/task.toml, sorted by metadata.order and then by work_name
+/// so that ids of newly synced tasks follow the intended order. Texts are left as written,
+/// they are stored in the shared database that way and localized with `localize` after loading.
+/// Falls back to the manifests embedded into the binary if there is no tasks directory
+pub fn load_manifests(tasks_dir: &Path) -> Result<Vec<TaskManifest>, LoadManifestError> {
+    let mut manifests = if tasks_dir.exists() {
+        load_manifests_from_dir(tasks_dir)?
+    } else {
+        embedded::TASK_MANIFESTS
+            .iter()
+            .map(|content| toml::from_str(content).map_err(LoadManifestError::EmbeddedParseError))
+            .collect::<Result<Vec<TaskManifest>, _>>()?
+    };
+
+    for manifest in &manifests {
+        manifest.test_execution_order()?;
+    }
+
+    manifests.sort_by_key(|m| (m.metadata.order.unwrap_or(i64::MAX), m.work_name.clone()));
+    Ok(manifests)
+}
+
+fn load_manifests_from_dir(tasks_dir: &Path) -> Result<Vec<TaskManifest>, LoadManifestError> {
+    let read_dir = fs::read_dir(tasks_dir)
+        .map_err(|e| LoadManifestError::IoError(tasks_dir.to_path_buf(), e))?;
+
+    let mut manifests: Vec<TaskManifest> = Vec::new();
+    for entry in read_dir.flatten() {
+        let path = entry.path().join(MANIFEST_FILE_NAME);
+        if !path.is_file() {
+            continue;
+        }
+
+        let manifest = load_manifest(&path)?;
+        if manifests.iter().any(|m| m.work_name == manifest.work_name) {
+            return Err(LoadManifestError::DuplicateWorkName(manifest.work_name));
+        }
+        manifests.push(manifest);
+    }
+    Ok(manifests)
+}
@@ -0,0 +1,629 @@
+use crate::embedded;
+use chrono::{NaiveDate, NaiveDateTime, Utc};
+use rusqlite::{Connection, params};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::Path;
+use thiserror::Error;
+
+/// Migration directories are named like "2026-03-22-000000_add_bash_history",
+/// the prefix before the underscore is the version
+const VERSION_FORMAT: &str = "%Y-%m-%d-%H%M%S";
+const VERSION_LENGTH: usize = "2026-03-22-000000".len();
+
+/// Old migrations were named like "22032026_add_bash_history", such names may still be
+/// recorded in schema_migrations of existing databases
+const LEGACY_VERSION_FORMAT: &str = "%d%m%Y";
+const LEGACY_VERSION_LENGTH: usize = "22032026".len();
+
+/// Migrations that inserted tasks before they moved to manifests. Their files are gone,
+/// but existing databases still record them as applied
+const RETIRED_MIGRATIONS: &[&str] = &[
+    "2026-02-23-000000_branching",
+    "2026-02-23-000000_hello_world",
+    "2026-03-18-000000_merge_conflict",
+    "2026-03-23-000000_forget_about_that_1",
+    "2026-03-24-000000_just_push_it",
+    "2026-03-24-000000_small_but_mighty",
+];
+
+#[derive(Debug, Error)]
+pub enum MigrationError {
+    #[error("While reading migrations: {0}")]
+    IoError(#[from] std::io::Error),
+
+    #[error("While working with SQL: {0}")]
+    SQLiteError(#[from] rusqlite::Error),
+
+    #[error("Migration name \"{0}\" has no valid version prefix")]
+    InvalidName(String),
+
+    #[error("Migrations \"{0}\" and \"{1}\" have the same version")]
+    DuplicateVersion(String, String),
+
+    #[error("Migration \"{0}\" was edited after it had been applied")]
+    ChecksumMismatch(String),
+
+    #[error("Migration \"{0}\" has no down.sql")]
+    Irreversible(String),
+
+    #[error("Migration \"{0}\" is applied, but its files are missing")]
+    Missing(String),
+}
+
+pub struct Migration {
+    pub version: i64,
+    pub name: String,
+    pub up_sql: String,
+    pub down_sql: Option<String>,
+    pub checksum: String,
+}
+
+impl Migration {
+    fn new(name: String, up_sql: String, down_sql: Option<String>) -> Result<Self, MigrationError> {
+        let version =
+            parse_version(&name).ok_or_else(|| MigrationError::InvalidName(name.clone()))?;
+        Ok(Migration {
+            version,
+            checksum: checksum(&up_sql),
+            name,
+            up_sql,
+            down_sql,
+        })
+    }
+}
+
+pub struct AppliedMigration {
+    pub version: i64,
+    pub name: String,
+    pub checksum: Option<String>,
+    pub applied_at: String,
+}
+
+pub enum MigrationState {
+    Applied(String),
+    Pending,
+    Modified,
+    Missing(String),
+}
+
+pub struct MigrationStatus {
+    pub version: i64,
+    pub name: String,
+    pub state: MigrationState,
+}
+
+pub fn checksum(sql: &str) -> String {
+    format!("{:x}", Sha256::digest(sql.as_bytes()))
+}
+
+/// Turns "2026-03-22-000000_name" into 20260322000000
+pub fn parse_version(name: &str) -> Option<i64> {
+    let prefix = name.get(..VERSION_LENGTH)?;
+    if name.as_bytes().get(VERSION_LENGTH) != Some(&b'_') {
+        return None;
+    }
+    let version = NaiveDateTime::parse_from_str(prefix, VERSION_FORMAT).ok()?;
+    version.format("%Y%m%d%H%M%S").to_string().parse().ok()
+}
+
+/// Turns legacy "22032026_name" into "2026-03-22-000000_name"
+fn upgrade_legacy_name(name: &str) -> Option<String> {
+    let prefix = name.get(..LEGACY_VERSION_LENGTH)?;
+    let rest = name.get(LEGACY_VERSION_LENGTH..)?;
+    if !rest.starts_with('_') {
+        return None;
+    }
+    let date = NaiveDate::parse_from_str(prefix, LEGACY_VERSION_FORMAT).ok()?;
+    Some(format!("{}-000000{}", date.format("%Y-%m-%d"), rest))
+}
+
+/// Reads migrations from the directory if it exists, otherwise falls back to the ones
+/// embedded into the binary. The result is sorted by version
+pub fn load_migrations(migrations_dir: &Path) -> Result<Vec<Migration>, MigrationError> {
+    let mut migrations = Vec::new();
+
+    if migrations_dir.exists() {
+        for entry in fs::read_dir(migrations_dir)?.flatten() {
+            let up_path = entry.path().join("up.sql");
+            if !up_path.is_file() {
+                continue;
+            }
+
+            let down_path = entry.path().join("down.sql");
+            let down_sql = if down_path.is_file() {
+                Some(fs::read_to_string(&down_path)?)
+            } else {
+                None
+            };
+
+            migrations.push(Migration::new(
+                entry.file_name().to_string_lossy().to_string(),
+                fs::read_to_string(&up_path)?,
+                down_sql,
+            )?);
+        }
+    } else {
+        for migration in embedded::MIGRATIONS {
+            migrations.push(Migration::new(
+                migration.name.to_string(),
+                migration.up.to_string(),
+                migration.down.map(str::to_string),
+            )?);
+        }
+    }
+
+    migrations.sort_by_key(|migration| migration.version);
+    for pair in migrations.windows(2) {
+        if pair[0].version == pair[1].version {
+            return Err(MigrationError::DuplicateVersion(
+                pair[0].name.clone(),
+                pair[1].name.clone(),
+            ));
+        }
+    }
+
+    Ok(migrations)
+}
+
+/// Creates schema_migrations and brings tables created by older versions up to date,
+/// forgetting retired migrations
+fn ensure_migrations_table(conn: &mut Connection) -> Result<(), MigrationError> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS schema_migrations (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            migration_name TEXT NOT NULL UNIQUE,
+            applied_at TEXT NOT NULL,
+            version INTEGER,
+            checksum TEXT
+        )",
+    )?;
+
+    let columns = {
+        let mut stmt = conn.prepare("SELECT name FROM pragma_table_info('schema_migrations')")?;
+        stmt.query_map([], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<String>, _>>()?
+    };
+    if !columns.iter().any(|column| column == "version") {
+        conn.execute_batch("ALTER TABLE schema_migrations ADD COLUMN version INTEGER")?;
+    }
+    if !columns.iter().any(|column| column == "checksum") {
+        conn.execute_batch("ALTER TABLE schema_migrations ADD COLUMN checksum TEXT")?;
+    }
+
+    let unversioned = {
+        let mut stmt =
+            conn.prepare("SELECT id, migration_name FROM schema_migrations WHERE version IS NULL")?;
+        stmt.query_map([], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+        })?
+        .collect::<Result<Vec<(i64, String)>, _>>()?
+    };
+
+    let tx = conn.transaction()?;
+    for (id, name) in unversioned {
+        let name = upgrade_legacy_name(&name).unwrap_or(name);
+        let version =
+            parse_version(&name).ok_or_else(|| MigrationError::InvalidName(name.clone()))?;
+        tx.execute(
+            "UPDATE schema_migrations SET migration_name = ?1, version = ?2 WHERE id = ?3",
+            params![name, version, id],
+        )?;
+    }
+    // Задания теперь синхронизируются из манифестов, записи о старых миграциях не нужны
+    for name in RETIRED_MIGRATIONS {
+        tx.execute(
+            "DELETE FROM schema_migrations WHERE migration_name = ?1",
+            params![name],
+        )?;
+    }
+    tx.commit()?;
+
+    Ok(())
+}
+
+fn applied_migrations(conn: &Connection) -> Result<Vec<AppliedMigration>, MigrationError> {
+    let mut stmt = conn.prepare(
+        "SELECT version, migration_name, checksum, applied_at
+         FROM schema_migrations
+         ORDER BY version, migration_name",
+    )?;
+    let applied = stmt
+        .query_map([], |row| {
+            Ok(AppliedMigration {
+                version: row.get(0)?,
+                name: row.get(1)?,
+                checksum: row.get(2)?,
+                applied_at: row.get(3)?,
+            })
+        })?
+        .collect::<Result<Vec<AppliedMigration>, _>>()?;
+    Ok(applied)
+}
+
+/// Fails if an applied migration was edited afterwards. Migrations applied before
+/// checksums were recorded get the current checksum
+fn verify_checksums(conn: &mut Connection, migrations: &[Migration]) -> Result<(), MigrationError> {
+    for applied in applied_migrations(conn)? {
+        let Some(migration) = migrations.iter().find(|m| m.name == applied.name) else {
+            continue;
+        };
+
+        match applied.checksum {
+            Some(checksum) if checksum != migration.checksum => {
+                return Err(MigrationError::ChecksumMismatch(applied.name));
+            }
+            Some(_) => {}
+            None => {
+                conn.execute(
+                    "UPDATE schema_migrations SET checksum = ?1 WHERE migration_name = ?2",
+                    params![migration.checksum, migration.name],
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Applies pending migrations in version order up to `target` (inclusive) and returns
+/// names of the applied ones
+pub fn migrate_up(
+    conn: &mut Connection,
+    migrations: &[Migration],
+    target: Option<i64>,
+) -> Result<Vec<String>, MigrationError> {
+    ensure_migrations_table(conn)?;
+    verify_checksums(conn, migrations)?;
+
+    let applied = applied_migrations(conn)?;
+    let mut newly_applied = Vec::new();
+
+    for migration in migrations {
+        if target.is_some_and(|target| migration.version > target) {
+            break;
+        }
+        if applied.iter().any(|a| a.name == migration.name) {
+            continue;
+        }
+
+        let tx = conn.transaction()?;
+        tx.execute_batch(&migration.up_sql)?;
+        tx.execute(
+            "INSERT INTO schema_migrations (migration_name, applied_at, version, checksum)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                migration.name,
+                Utc::now().to_rfc3339(),
+                migration.version,
+                migration.checksum
+            ],
+        )?;
+        tx.commit()?;
+
+        newly_applied.push(migration.name.clone());
+    }
+
+    Ok(newly_applied)
+}
+
+/// Reverts `steps` latest applied migrations using their down.sql and returns names
+/// of the reverted ones
+pub fn migrate_down(
+    conn: &mut Connection,
+    migrations: &[Migration],
+    steps: usize,
+) -> Result<Vec<String>, MigrationError> {
+    ensure_migrations_table(conn)?;
+    verify_checksums(conn, migrations)?;
+
+    let mut reverted = Vec::new();
+    for applied in applied_migrations(conn)?.into_iter().rev().take(steps) {
+        let migration = migrations
+            .iter()
+            .find(|m| m.name == applied.name)
+            .ok_or_else(|| MigrationError::Missing(applied.name.clone()))?;
+        let down_sql = migration
+            .down_sql
+            .as_ref()
+            .ok_or_else(|| MigrationError::Irreversible(applied.name.clone()))?;
+
+        let tx = conn.transaction()?;
+        tx.execute_batch(down_sql)?;
+        tx.execute(
+            "DELETE FROM schema_migrations WHERE migration_name = ?1",
+            params![applied.name],
+        )?;
+        tx.commit()?;
+
+        reverted.push(applied.name);
+    }
+
+    Ok(reverted)
+}
+
+pub fn migration_status(
+    conn: &mut Connection,
+    migrations: &[Migration],
+) -> Result<Vec<MigrationStatus>, MigrationError> {
+    ensure_migrations_table(conn)?;
+    let applied = applied_migrations(conn)?;
+
+    let mut statuses: Vec<MigrationStatus> = migrations
+        .iter()
+        .map(|migration| {
+            let state = match applied.iter().find(|a| a.name == migration.name) {
+                Some(a)
+                    if a.checksum
+                        .as_ref()
+                        .is_some_and(|c| *c != migration.checksum) =>
+                {
+                    MigrationState::Modified
+                }
+                Some(a) => MigrationState::Applied(a.applied_at.clone()),
+                None => MigrationState::Pending,
+            };
+            MigrationStatus {
+                version: migration.version,
+                name: migration.name.clone(),
+                state,
+            }
+        })
+        .collect();
+
+    for a in applied {
+        if !migrations.iter().any(|m| m.name == a.name) {
+            statuses.push(MigrationStatus {
+                version: a.version,
+                name: a.name,
+                state: MigrationState::Missing(a.applied_at),
+            });
+        }
+    }
+
+    statuses.sort_by(|a, b| (a.version, &a.name).cmp(&(b.version, &b.name)));
+    Ok(statuses)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn migration(name: &str, up_sql: &str, down_sql: Option<&str>) -> Migration {
+        Migration::new(
+            name.to_string(),
+            up_sql.to_string(),
+            down_sql.map(str::to_string),
+        )
+        .unwrap()
+    }
+
+    fn migrations() -> Vec<Migration> {
+        vec![
+            migration(
+                "2026-01-01-000000_users",
+                "CREATE TABLE users (id INTEGER PRIMARY KEY);",
+                Some("DROP TABLE users;"),
+            ),
+            migration(
+                "2026-01-02-000000_tasks",
+                "CREATE TABLE tasks (id INTEGER PRIMARY KEY);",
+                Some("DROP TABLE tasks;"),
+            ),
+            migration(
+                "2026-01-03-120000_attempts",
+                "CREATE TABLE attempts (id INTEGER PRIMARY KEY);",
+                Some("DROP TABLE attempts;"),
+            ),
+        ]
+    }
+
+    fn table_exists(conn: &Connection, table: &str) -> bool {
+        conn.query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?1",
+            [table],
+            |row| row.get::<_, i64>(0),
+        )
+        .unwrap()
+            == 1
+    }
+
+    #[test]
+    fn versions() {
+        assert_eq!(
+            parse_version("2026-03-22-000000_add_bash_history"),
+            Some(20260322000000)
+        );
+        assert_eq!(parse_version("2026-03-22-000000"), None);
+        assert_eq!(parse_version("2026-13-22-000000_bad_month"), None);
+        assert_eq!(parse_version("22032026_add_bash_history"), None);
+
+        assert_eq!(
+            upgrade_legacy_name("22032026_add_bash_history").as_deref(),
+            Some("2026-03-22-000000_add_bash_history")
+        );
+        assert_eq!(upgrade_legacy_name("32032026_bad_day"), None);
+        assert_eq!(
+            upgrade_legacy_name("2026-03-22-000000_add_bash_history"),
+            None
+        );
+    }
+
+    #[test]
+    fn legacy_names_are_upgraded() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE schema_migrations (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                migration_name TEXT NOT NULL UNIQUE,
+                applied_at TEXT NOT NULL
+            );
+            CREATE TABLE users (id INTEGER PRIMARY KEY);
+            INSERT INTO schema_migrations (migration_name, applied_at)
+            VALUES ('01012026_users', '2026-01-01T00:00:00+00:00'),
+                   ('23022026_hello_world', '2026-02-23T00:00:00+00:00');",
+        )
+        .unwrap();
+
+        let statuses = migration_status(&mut conn, &migrations()).unwrap();
+        assert_eq!(statuses.len(), 3);
+        assert_eq!(statuses[0].version, 20260101000000);
+        assert!(matches!(statuses[0].state, MigrationState::Applied(_)));
+        assert!(matches!(statuses[1].state, MigrationState::Pending));
+        assert!(matches!(statuses[2].state, MigrationState::Pending));
+
+        // Старая запись без контрольной суммы получает текущую
+        let applied = migrate_up(&mut conn, &migrations(), None).unwrap();
+        assert_eq!(
+            applied,
+            ["2026-01-02-000000_tasks", "2026-01-03-120000_attempts"]
+        );
+        let checksums = applied_migrations(&conn).unwrap();
+        assert!(checksums.iter().all(|a| a.checksum.is_some()));
+    }
+
+    #[test]
+    fn up_to_version() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        let applied = migrate_up(&mut conn, &migrations(), Some(20260102000000)).unwrap();
+        assert_eq!(
+            applied,
+            ["2026-01-01-000000_users", "2026-01-02-000000_tasks"]
+        );
+        assert!(table_exists(&conn, "tasks"));
+        assert!(!table_exists(&conn, "attempts"));
+
+        let applied = migrate_up(&mut conn, &migrations(), None).unwrap();
+        assert_eq!(applied, ["2026-01-03-120000_attempts"]);
+        assert!(
+            migrate_up(&mut conn, &migrations(), None)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn down_steps() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        migrate_up(&mut conn, &migrations(), None).unwrap();
+
+        let reverted = migrate_down(&mut conn, &migrations(), 2).unwrap();
+        assert_eq!(
+            reverted,
+            ["2026-01-03-120000_attempts", "2026-01-02-000000_tasks"]
+        );
+        assert!(table_exists(&conn, "users"));
+        assert!(!table_exists(&conn, "tasks"));
+
+        let statuses = migration_status(&mut conn, &migrations()).unwrap();
+        assert!(matches!(statuses[0].state, MigrationState::Applied(_)));
+        assert!(matches!(statuses[1].state, MigrationState::Pending));
+        assert!(matches!(statuses[2].state, MigrationState::Pending));
+    }
+
+    #[test]
+    fn irreversible_migration() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        let migrations = [migration(
+            "2026-01-01-000000_users",
+            "CREATE TABLE users (id INTEGER PRIMARY KEY);",
+            None,
+        )];
+        migrate_up(&mut conn, &migrations, None).unwrap();
+        assert!(matches!(
+            migrate_down(&mut conn, &migrations, 1),
+            Err(MigrationError::Irreversible(_))
+        ));
+    }
+
+    #[test]
+    fn modified_migration() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        migrate_up(&mut conn, &migrations(), None).unwrap();
+
+        let mut edited = migrations();
+        edited[1]
+            .up_sql
+            .push_str("\nCREATE TABLE extra (id INTEGER PRIMARY KEY);");
+        edited[1].checksum = checksum(&edited[1].up_sql);
+
+        let statuses = migration_status(&mut conn, &edited).unwrap();
+        assert!(matches!(statuses[1].state, MigrationState::Modified));
+        assert!(matches!(
+            migrate_up(&mut conn, &edited, None),
+            Err(MigrationError::ChecksumMismatch(name)) if name == "2026-01-02-000000_tasks"
+        ));
+    }
+
+    #[test]
+    fn missing_migration() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        migrate_up(&mut conn, &migrations(), None).unwrap();
+
+        let mut remaining = migrations();
+        remaining.pop();
+
+        let statuses = migration_status(&mut conn, &remaining).unwrap();
+        assert_eq!(statuses.len(), 3);
+        assert!(matches!(statuses[2].state, MigrationState::Missing(_)));
+        assert!(matches!(
+            migrate_down(&mut conn, &remaining, 1),
+            Err(MigrationError::Missing(name)) if name == "2026-01-03-120000_attempts"
+        ));
+    }
+
+    #[test]
+    fn retired_migrations_are_forgotten() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE schema_migrations (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                migration_name TEXT NOT NULL UNIQUE,
+                applied_at TEXT NOT NULL
+            );
+            INSERT INTO schema_migrations (migration_name, applied_at)
+            VALUES ('23022026_hello_world', '2026-02-23T00:00:00+00:00'),
+                   ('24032026_just_push_it', '2026-03-24T00:00:00+00:00');",
+        )
+        .unwrap();
+
+        let statuses = migration_status(&mut conn, &migrations()).unwrap();
+        assert!(
+            statuses
+                .iter()
+                .all(|status| matches!(status.state, MigrationState::Pending))
+        );
+    }
+
+    #[test]
+    fn embedded_migrations_apply_to_schema() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(embedded::SCHEMA).unwrap();
+
+        let migrations = load_migrations(Path::new("/nonexistent")).unwrap();
+        let applied = migrate_up(&mut conn, &migrations, None).unwrap();
+        assert_eq!(applied.len(), migrations.len());
+    }
+
+    #[test]
+    fn deleting_user_removes_unlocked_hints() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(embedded::SCHEMA).unwrap();
+        let migrations = load_migrations(Path::new("/nonexistent")).unwrap();
+        migrate_up(&mut conn, &migrations, None).unwrap();
+
+        conn.execute_batch(
+            "INSERT INTO users (id, username, created_at) VALUES (1, 'student', '');
+             INSERT INTO tasks (id, name, work_name, description, extended_description)
+                 VALUES (1, 'Hello', 'hello-world', '', '');
+             INSERT INTO unlocked_hints (user_id, task_id, hint_index, unlocked_at)
+                 VALUES (1, 1, 0, '');",
+        )
+        .unwrap();
+        conn.execute("DELETE FROM users WHERE id = 1", []).unwrap();
+
+        let hints: i64 = conn
+            .query_row("SELECT COUNT(*) FROM unlocked_hints", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(hints, 0);
+    }
+}
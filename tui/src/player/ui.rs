@@ -0,0 +1,146 @@
+use crate::Frame;
+use crate::app::{App, VERSION};
+use crate::locale::{tr, tr_fmt};
+use crate::recording::{self, RecordingError, RecordingEvent};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::DefaultTerminal;
+use ratatui::layout::{Alignment, Constraint};
+use ratatui::prelude::{Direction, Layout};
+use ratatui::style::{Modifier, Style, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Paragraph};
+use std::io;
+use std::path::Path;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+use tui_term::{vt100, widget::PseudoTerminal};
+use vt100::Screen;
+
+/// Playback speeds switched with ← and →
+const SPEEDS: [f64; 6] = [0.5, 1.0, 2.0, 4.0, 8.0, 16.0];
+const DEFAULT_SPEED: usize = 1;
+
+#[derive(Debug, Error)]
+pub enum PlayRecordingError {
+    #[error("{0}")]
+    RecordingError(#[from] RecordingError),
+
+    #[error("IO error: {0}")]
+    DrawTerminalError(#[from] io::Error),
+}
+
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    format!("{:02}:{:02}", secs / 60, secs % 60)
+}
+
+impl App {
+    fn render_player(&self, frame: &mut Frame, screen: &Screen, status: String) {
+        let title = Line::from(format!("git-trainer v{}", VERSION).bold()).centered();
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1),
+                Constraint::Min(1),
+                Constraint::Length(1),
+            ])
+            .split(frame.area());
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(tr_fmt(
+                "player.title",
+                &[("task", &self.task_under_cursor().name)],
+            ))
+            .style(Style::default().add_modifier(Modifier::BOLD));
+        let pseudo_term = PseudoTerminal::new(screen).block(block);
+        let explanation = Paragraph::new(status)
+            .style(Style::default())
+            .alignment(Alignment::Center);
+        frame.render_widget(title, chunks[0]);
+        frame.render_widget(pseudo_term, chunks[1]);
+        frame.render_widget(explanation, chunks[2]);
+    }
+
+    /// Replays the recording of the attempt under cursor until the user quits
+    pub async fn play_recording(
+        &mut self,
+        terminal: &mut DefaultTerminal,
+    ) -> Result<(), PlayRecordingError> {
+        let Some(path) = self
+            .attempt_under_cursor()
+            .and_then(|attempt| attempt.recording.clone())
+        else {
+            return Ok(());
+        };
+        let recording = recording::load_recording(Path::new(&path))?;
+        let total = recording.duration();
+
+        let mut parser = vt100::Parser::new(recording.rows, recording.cols, 0);
+        let mut next_event = 0;
+        let mut position = Duration::ZERO;
+        let mut speed = DEFAULT_SPEED;
+        let mut paused = false;
+        let mut last_tick = Instant::now();
+
+        loop {
+            let now = Instant::now();
+            if !paused {
+                position += now.duration_since(last_tick).mul_f64(SPEEDS[speed]);
+            }
+            last_tick = now;
+
+            while let Some((time, event)) = recording.events.get(next_event) {
+                if *time > position {
+                    break;
+                }
+                match event {
+                    RecordingEvent::Output(data) => parser.process(data.as_bytes()),
+                    RecordingEvent::Resize { cols, rows } => {
+                        parser.screen_mut().set_size(*rows, *cols)
+                    }
+                }
+                next_event += 1;
+            }
+
+            let finished = next_event == recording.events.len();
+            let state = if finished {
+                tr("player.finished")
+            } else if paused {
+                tr("player.paused")
+            } else {
+                tr("player.playing")
+            };
+            let status = tr_fmt(
+                "player.status",
+                &[
+                    ("position", &format_duration(position.min(total))),
+                    ("duration", &format_duration(total)),
+                    ("speed", &SPEEDS[speed]),
+                    ("state", &state),
+                ],
+            );
+            terminal.draw(|f| self.render_player(f, parser.screen(), status))?;
+
+            if event::poll(Duration::from_millis(20))?
+                && let Event::Key(key) = event::read()?
+                && key.kind == KeyEventKind::Press
+            {
+                match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                    KeyCode::Char(' ') => paused = !paused,
+                    KeyCode::Right | KeyCode::Char('l') => {
+                        speed = (speed + 1).min(SPEEDS.len() - 1)
+                    }
+                    KeyCode::Left | KeyCode::Char('h') => speed = speed.saturating_sub(1),
+                    KeyCode::Char('r') => {
+                        parser = vt100::Parser::new(recording.rows, recording.cols, 0);
+                        next_event = 0;
+                        position = Duration::ZERO;
+                        paused = false;
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+}
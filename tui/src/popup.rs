@@ -1,4 +1,5 @@
 use crate::Frame;
+use crate::locale::tr;
 use ratatui::layout::Constraint;
 use ratatui::layout::{Alignment, Flex};
 use ratatui::prelude::{Layout, Rect};
@@ -16,6 +17,8 @@ pub enum Popup {
     RunConifrmation,
     ResetConfirmation,
     ResetDone,
+    /// The task container was created from another image or version of the task
+    ContainerOutdated,
     Error(String),
 }
 
@@ -31,17 +34,13 @@ impl Popup {
     fn config(&self, frame: &mut Frame) -> PopupConfig {
         match self {
             Popup::RunConifrmation => PopupConfig {
-                title: Some("Подтвердите выбор".to_string()),
+                title: Some(tr("popup.run_title").to_string()),
                 lines: vec![
-                    Line::from("Начать выполнение задания?").fg(Color::LightBlue),
-                    Line::from("Enter — подтвердить, Esc — отменить").fg(Color::LightBlue),
+                    Line::from(tr("popup.run_question")).fg(Color::LightBlue),
+                    Line::from(tr("popup.confirm_or_cancel")).fg(Color::LightBlue),
                     Line::from(""),
-                    Line::from(
-                        "Внутри вы можете посмотреть условие задания командой git-trainer task",
-                    )
-                    .fg(Color::LightBlue),
-                    Line::from("А сдать задание можно с помощью git-trainer submit")
-                        .fg(Color::LightBlue),
+                    Line::from(tr("popup.run_task_hint")).fg(Color::LightBlue),
+                    Line::from(tr("popup.run_submit_hint")).fg(Color::LightBlue),
                 ],
                 color: Color::LightBlue,
                 width: std::cmp::max(frame.area().width / 3, 69),
@@ -49,23 +48,37 @@ impl Popup {
             },
 
             Popup::ResetConfirmation => PopupConfig {
-                title: Some("Подтвердите перезагрузку".to_string()),
+                title: Some(tr("popup.reset_title").to_string()),
                 lines: vec![
-                    Line::from("Перезагрузить задание?").fg(Color::LightBlue),
-                    Line::from("Вы потеряете все свои изменения.").fg(Color::LightBlue),
-                    Line::from("Enter — подтвердить, Esc — отменить").fg(Color::LightBlue),
+                    Line::from(tr("popup.reset_question")).fg(Color::LightBlue),
+                    Line::from(tr("popup.reset_warning")).fg(Color::LightBlue),
+                    Line::from(tr("popup.confirm_or_cancel")).fg(Color::LightBlue),
                 ],
                 color: Color::LightBlue,
                 width: std::cmp::max(frame.area().width / 3, 35),
                 height: std::cmp::max(frame.area().height / 3, 6),
             },
 
+            Popup::ContainerOutdated => PopupConfig {
+                title: Some(tr("popup.outdated_title").to_string()),
+                lines: vec![
+                    Line::from(tr("popup.outdated_reason")).fg(Color::Yellow),
+                    Line::from(""),
+                    Line::from(tr("popup.outdated_keep_repository")).fg(Color::Yellow),
+                    Line::from(tr("popup.outdated_from_scratch")).fg(Color::Yellow),
+                    Line::from(tr("popup.outdated_continue")).fg(Color::Yellow),
+                ],
+                color: Color::Yellow,
+                width: std::cmp::max(frame.area().width / 3, 69),
+                height: std::cmp::max(frame.area().height / 3, 8),
+            },
+
             Popup::Error(error) => PopupConfig {
-                title: Some("Ошибка!".to_string()),
+                title: Some(tr("popup.error_title").to_string()),
                 lines: vec![
                     Line::from(error.clone()).fg(Color::Red),
                     Line::from(""),
-                    Line::from("Обратитесь к преподавателю.").fg(Color::Red),
+                    Line::from(tr("popup.error_contact")).fg(Color::Red),
                 ],
                 color: Color::Red,
                 width: frame.area().width * 2 / 3,
@@ -75,8 +88,8 @@ impl Popup {
             Popup::ResetDone => PopupConfig {
                 title: None,
                 lines: vec![
-                    Line::from("Задание перезагружено.").fg(Color::LightGreen),
-                    Line::from("Нажмите Enter, чтобы продолжить").fg(Color::LightGreen),
+                    Line::from(tr("popup.reset_done")).fg(Color::LightGreen),
+                    Line::from(tr("popup.press_enter")).fg(Color::LightGreen),
                 ],
                 color: Color::LightGreen,
                 width: frame.area().width / 3,
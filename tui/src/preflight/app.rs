@@ -0,0 +1,31 @@
+use crate::app::{App, AppStatus};
+use crate::preflight;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::DefaultTerminal;
+use std::io;
+
+impl App {
+    /// Runs the preflight checks, the screen says they are in progress meanwhile
+    pub async fn run_preflight(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        self.preflight = None;
+        terminal.draw(|f| self.render_preflight(f))?;
+        self.preflight =
+            Some(preflight::run_checks(&mut self.repo, &self.context.tasks, &self.manifests).await);
+        Ok(())
+    }
+
+    pub fn preflight_handle_events(&mut self) -> io::Result<()> {
+        if let Event::Key(key_event) = event::read()?
+            && key_event.kind == KeyEventKind::Press
+        {
+            match key_event.code {
+                KeyCode::Enter | KeyCode::Esc => self.status = AppStatus::Idling,
+                KeyCode::Char('r') => self.status = AppStatus::RunningPreflight,
+                KeyCode::Char('b') => self.status = AppStatus::BuildingImages,
+                KeyCode::Char('q') => self.status = AppStatus::Exiting,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
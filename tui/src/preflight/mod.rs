@@ -0,0 +1,249 @@
+pub mod app;
+pub mod ui;
+
+use crate::config::config;
+use crate::db::{Repo, Task};
+use crate::docker;
+use crate::images::{self, CONTENT_HASH_LABEL};
+use crate::locale::{tr, tr_fmt};
+use crate::manifest::TaskManifest;
+use crate::migrations::MigrationState;
+use std::collections::HashMap;
+use std::fs;
+use std::time::Duration;
+
+/// How long the Docker daemon may take to answer before it is considered unreachable
+const DOCKER_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Outcome of a single check, with a short explanation either way
+pub enum Check {
+    Passed(String),
+    Failed(String),
+    /// Usable, but something should be fixed, e.g. an image built from old sources
+    Warning(String),
+    /// Not performed because a check it needs has failed, e.g. images without Docker
+    Skipped,
+}
+
+impl Check {
+    pub fn is_passed(&self) -> bool {
+        matches!(self, Check::Passed(_))
+    }
+}
+
+pub enum TaskReadiness {
+    Ready,
+    MissingImage,
+    /// The image is built from sources that have changed since
+    StaleImage,
+    MissingTests,
+    /// The image could not be checked
+    Unknown,
+}
+
+pub struct TaskChecks {
+    pub work_name: String,
+    pub image: Check,
+    pub tests: Check,
+}
+
+impl TaskChecks {
+    pub fn readiness(&self) -> TaskReadiness {
+        match (&self.image, &self.tests) {
+            (Check::Failed(_), _) => TaskReadiness::MissingImage,
+            (_, Check::Failed(_)) => TaskReadiness::MissingTests,
+            (Check::Skipped, _) | (_, Check::Skipped) => TaskReadiness::Unknown,
+            (Check::Warning(_), _) => TaskReadiness::StaleImage,
+            _ => TaskReadiness::Ready,
+        }
+    }
+}
+
+pub struct PreflightReport {
+    pub docker: Check,
+    pub database: Check,
+    pub schema: Check,
+    pub tasks: Vec<TaskChecks>,
+}
+
+impl PreflightReport {
+    /// Stale images do not count, tasks can still be run with them
+    pub fn is_ok(&self) -> bool {
+        self.docker.is_passed()
+            && self.database.is_passed()
+            && self.schema.is_passed()
+            && self.tasks.iter().all(|task| {
+                matches!(
+                    task.readiness(),
+                    TaskReadiness::Ready | TaskReadiness::StaleImage
+                )
+            })
+    }
+
+    pub fn task(&self, work_name: &str) -> Option<&TaskChecks> {
+        self.tasks.iter().find(|task| task.work_name == work_name)
+    }
+}
+
+async fn check_docker() -> Check {
+    match tokio::time::timeout(DOCKER_TIMEOUT, docker::daemon_version()).await {
+        Ok(Ok(version)) => Check::Passed(version),
+        Ok(Err(e)) => Check::Failed(e.to_string()),
+        Err(_) => Check::Failed(tr_fmt(
+            "preflight.docker_timeout",
+            &[("secs", &DOCKER_TIMEOUT.as_secs())],
+        )),
+    }
+}
+
+fn check_database(repo: &Repo) -> Check {
+    match repo.check_writable() {
+        Ok(true) => Check::Passed(config().paths.database.display().to_string()),
+        Ok(false) => Check::Failed(tr("preflight.database_readonly").to_string()),
+        Err(e) => Check::Failed(e.to_string()),
+    }
+}
+
+/// Every migration known to the binary or found on disk must be applied and unchanged
+fn check_schema(repo: &mut Repo) -> Check {
+    let statuses = match repo.migration_status() {
+        Ok(statuses) => statuses,
+        Err(e) => return Check::Failed(e.to_string()),
+    };
+
+    let mut pending = Vec::new();
+    for status in &statuses {
+        match &status.state {
+            MigrationState::Applied(_) => {}
+            MigrationState::Pending => pending.push(status.name.as_str()),
+            MigrationState::Modified => {
+                return Check::Failed(tr_fmt(
+                    "preflight.schema_modified",
+                    &[("name", &status.name)],
+                ));
+            }
+            MigrationState::Missing(_) => {
+                return Check::Failed(tr_fmt(
+                    "preflight.schema_missing",
+                    &[("name", &status.name)],
+                ));
+            }
+        }
+    }
+    if !pending.is_empty() {
+        return Check::Failed(tr_fmt(
+            "preflight.schema_pending",
+            &[("names", &pending.join(", "))],
+        ));
+    }
+
+    match statuses.last() {
+        Some(latest) => Check::Passed(tr_fmt(
+            "preflight.schema_version",
+            &[("version", &latest.version)],
+        )),
+        None => Check::Passed(tr("preflight.schema_empty").to_string()),
+    }
+}
+
+/// An image is stale if the build context is at hand and its sources hash differently now
+async fn check_image(task: &Task, docker_available: bool, expected_hash: Option<&String>) -> Check {
+    if !docker_available {
+        return Check::Skipped;
+    }
+    match docker::image_exists(&task.image_name).await {
+        Ok(true) => {
+            let Some(expected_hash) = expected_hash else {
+                return Check::Passed(task.image_name.clone());
+            };
+            match docker::image_label(&task.image_name, CONTENT_HASH_LABEL).await {
+                Ok(hash) if hash.as_ref() == Some(expected_hash) => {
+                    Check::Passed(task.image_name.clone())
+                }
+                Ok(_) => Check::Warning(tr_fmt(
+                    "preflight.image_outdated",
+                    &[("image", &task.image_name), ("task", &task.work_name)],
+                )),
+                Err(e) => Check::Failed(e.to_string()),
+            }
+        }
+        Ok(false) => Check::Failed(tr_fmt(
+            "preflight.image_missing",
+            &[("image", &task.image_name)],
+        )),
+        Err(e) => Check::Failed(e.to_string()),
+    }
+}
+
+/// The scripts listed in the manifest must exist, without a list the directory must not be empty
+fn check_tests(task: &Task, manifest: Option<&TaskManifest>) -> Check {
+    let dir = config().paths.tests.join(&task.work_name);
+    if !dir.is_dir() {
+        return Check::Failed(tr_fmt(
+            "preflight.tests_missing_dir",
+            &[("path", &dir.display())],
+        ));
+    }
+
+    let count = match manifest {
+        Some(manifest) if !manifest.tests.is_empty() => {
+            for test in &manifest.tests {
+                let script = dir.join(&test.script);
+                if !script.is_file() {
+                    return Check::Failed(tr_fmt(
+                        "preflight.tests_missing_script",
+                        &[("path", &script.display())],
+                    ));
+                }
+            }
+            manifest.tests.len()
+        }
+        _ => fs::read_dir(&dir)
+            .map(|entries| entries.count())
+            .unwrap_or(0),
+    };
+    if count == 0 {
+        return Check::Failed(tr_fmt("preflight.tests_empty", &[("path", &dir.display())]));
+    }
+    Check::Passed(tr_fmt("preflight.tests_count", &[("count", &count)]))
+}
+
+/// Checks everything a task needs to be started and graded
+pub async fn run_checks(
+    repo: &mut Repo,
+    tasks: &[Task],
+    manifests: &[TaskManifest],
+) -> PreflightReport {
+    let docker = check_docker().await;
+    let database = check_database(repo);
+    let schema = check_schema(repo);
+
+    // Без Docker образы не проверяются, и хешировать их исходники незачем
+    let context = &config().paths.build_context;
+    let expected_hashes = match images::build_targets(context, None) {
+        Ok(targets) if docker.is_passed() => images::expected_hashes(context, &targets),
+        _ => HashMap::new(),
+    };
+
+    let mut task_checks = Vec::new();
+    for task in tasks {
+        let manifest = manifests.iter().find(|m| m.work_name == task.work_name);
+        task_checks.push(TaskChecks {
+            work_name: task.work_name.clone(),
+            image: check_image(
+                task,
+                docker.is_passed(),
+                expected_hashes.get(&task.work_name),
+            )
+            .await,
+            tests: check_tests(task, manifest),
+        });
+    }
+
+    PreflightReport {
+        docker,
+        database,
+        schema,
+        tasks: task_checks,
+    }
+}
@@ -0,0 +1,90 @@
+use crate::Frame;
+use crate::app::{App, VERSION};
+use crate::locale::tr;
+use crate::preflight::{Check, TaskReadiness};
+use ratatui::layout::{Alignment, Constraint, Direction, Layout};
+use ratatui::style::{Color, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+
+fn check_line(label: &str, check: &Check) -> Line<'static> {
+    let result = match check {
+        Check::Passed(detail) => format!("✔ {}", detail).light_green(),
+        Check::Failed(reason) => format!("✘ {}", reason).light_red(),
+        Check::Warning(reason) => format!("! {}", reason).yellow(),
+        Check::Skipped => format!("– {}", tr("preflight.skipped")).dark_gray(),
+    };
+    Line::from(vec![format!("{}: ", label).bold(), result])
+}
+
+/// Short readiness of a task for the main menu, None while the checks are running
+pub fn readiness_span(readiness: Option<TaskReadiness>) -> Span<'static> {
+    match readiness {
+        None => Span::from(tr("readiness.checking")).fg(Color::DarkGray),
+        Some(TaskReadiness::Ready) => format!("✔ {}", tr("readiness.ready")).fg(Color::LightGreen),
+        Some(TaskReadiness::MissingImage) => {
+            format!("✘ {}", tr("readiness.missing_image")).fg(Color::Red)
+        }
+        Some(TaskReadiness::StaleImage) => {
+            format!("! {}", tr("readiness.stale_image")).fg(Color::Yellow)
+        }
+        Some(TaskReadiness::MissingTests) => {
+            format!("✘ {}", tr("readiness.missing_tests")).fg(Color::Red)
+        }
+        Some(TaskReadiness::Unknown) => format!("? {}", tr("readiness.unknown")).fg(Color::Yellow),
+    }
+}
+
+impl App {
+    pub fn render_preflight(&self, frame: &mut Frame) {
+        let title = Line::from(format!("git-trainer v{}", VERSION).bold()).centered();
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1),
+                Constraint::Min(1),
+                Constraint::Length(1),
+            ])
+            .split(frame.area());
+
+        let lines = match &self.preflight {
+            None => vec![Line::from(tr("preflight.checking").dark_gray())],
+            Some(report) => {
+                let mut lines = vec![
+                    check_line(tr("preflight.docker"), &report.docker),
+                    check_line(tr("preflight.database"), &report.database),
+                    check_line(tr("preflight.schema"), &report.schema),
+                    Line::default(),
+                    Line::from(tr("preflight.tasks").bold()),
+                ];
+                for task in &self.context.tasks {
+                    let Some(checks) = report.task(&task.work_name) else {
+                        continue;
+                    };
+                    lines.push(Line::from(format!("{} ({})", task.name, task.work_name)));
+                    lines.push(check_line(
+                        &format!("  {}", tr("preflight.image")),
+                        &checks.image,
+                    ));
+                    lines.push(check_line(
+                        &format!("  {}", tr("preflight.tests")),
+                        &checks.tests,
+                    ));
+                }
+                lines
+            }
+        };
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(tr("preflight.title").bold());
+        let paragraph = Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap { trim: false });
+        let footer = Paragraph::new(tr("preflight.footer")).alignment(Alignment::Center);
+
+        frame.render_widget(title, chunks[0]);
+        frame.render_widget(paragraph, chunks[1]);
+        frame.render_widget(footer, chunks[2]);
+    }
+}
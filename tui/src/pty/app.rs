@@ -1,6 +1,9 @@
 use crate::App;
+use crate::pty::{copy_mode, keys};
 use bytes::Bytes;
 use crossterm::event::{KeyCode, KeyModifiers};
+use std::io;
+use tui_term::vt100::Screen;
 
 impl App {
     pub fn handle_popup_key(
@@ -16,76 +19,46 @@ impl App {
         }
     }
 
+    pub fn handle_copy_mode_key(&mut self, key: KeyCode, screen: &mut Screen) -> io::Result<()> {
+        let Some(copy_mode) = self.copy_mode.as_mut() else {
+            return Ok(());
+        };
+        let page = screen.size().0 as i64;
+
+        match key {
+            KeyCode::Esc | KeyCode::Char('q') => self.copy_mode = None,
+            KeyCode::Up | KeyCode::Char('k') => copy_mode.move_cursor(screen, -1, 0),
+            KeyCode::Down | KeyCode::Char('j') => copy_mode.move_cursor(screen, 1, 0),
+            KeyCode::Left | KeyCode::Char('h') => copy_mode.move_cursor(screen, 0, -1),
+            KeyCode::Right | KeyCode::Char('l') => copy_mode.move_cursor(screen, 0, 1),
+            KeyCode::PageUp => copy_mode.move_cursor(screen, -page, 0),
+            KeyCode::PageDown => copy_mode.move_cursor(screen, page, 0),
+            KeyCode::Home | KeyCode::Char('0') => {
+                copy_mode.move_cursor(screen, 0, -i64::from(u16::MAX))
+            }
+            KeyCode::End | KeyCode::Char('$') => {
+                copy_mode.move_cursor(screen, 0, i64::from(u16::MAX))
+            }
+            KeyCode::Char('v') | KeyCode::Char(' ') => copy_mode.toggle_selection(),
+            KeyCode::Char('y') | KeyCode::Enter => {
+                if let Some(text) = copy_mode.selected_text(screen) {
+                    copy_mode::copy_to_clipboard(&text)?;
+                    self.copy_mode = None;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
     pub fn handle_terminal_key(
         &mut self,
         key: KeyCode,
         modifiers: KeyModifiers,
+        screen: &Screen,
         sender: &tokio::sync::mpsc::Sender<Bytes>,
     ) -> Result<(), String> {
-        let bytes_to_send = if modifiers.contains(KeyModifiers::CONTROL) {
-            match key {
-                KeyCode::Char(c) if c.is_ascii_alphabetic() => {
-                    let byte = (c.to_ascii_lowercase() as u8) & 0x1F;
-                    vec![byte]
-                }
-                KeyCode::Char('[') => vec![27],
-                KeyCode::Char('\\') => vec![28],
-                KeyCode::Char(']') => vec![29],
-                KeyCode::Char('^') => vec![30],
-                KeyCode::Char('_') => vec![31],
-                KeyCode::Char('@') => vec![0],
-                _ => vec![],
-            }
-        } else if modifiers.contains(KeyModifiers::ALT) {
-            match key {
-                KeyCode::Char(c) => {
-                    let mut bytes = vec![27];
-                    let mut buf = [0; 4];
-                    bytes.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
-                    bytes
-                }
-                _ => vec![],
-            }
-        } else {
-            match key {
-                KeyCode::Enter => vec![13],
-                KeyCode::Tab => vec![9],
-                KeyCode::Backspace => vec![127],
-                KeyCode::Esc => vec![27],
-
-                KeyCode::Up => vec![27, 91, 65],
-                KeyCode::Down => vec![27, 91, 66],
-                KeyCode::Right => vec![27, 91, 67],
-                KeyCode::Left => vec![27, 91, 68],
-
-                KeyCode::Home => vec![27, 91, 72],
-                KeyCode::End => vec![27, 91, 70],
-                KeyCode::PageUp => vec![27, 91, 53, 126],
-                KeyCode::PageDown => vec![27, 91, 54, 126],
-                KeyCode::Insert => vec![27, 91, 50, 126],
-                KeyCode::Delete => vec![27, 91, 51, 126],
-
-                KeyCode::F(1) => vec![27, 79, 80],
-                KeyCode::F(2) => vec![27, 79, 81],
-                KeyCode::F(3) => vec![27, 79, 82],
-                KeyCode::F(4) => vec![27, 79, 83],
-                KeyCode::F(5) => vec![27, 91, 49, 53, 126],
-                KeyCode::F(6) => vec![27, 91, 49, 55, 126],
-                KeyCode::F(7) => vec![27, 91, 49, 56, 126],
-                KeyCode::F(8) => vec![27, 91, 49, 57, 126],
-                KeyCode::F(9) => vec![27, 91, 50, 48, 126],
-                KeyCode::F(10) => vec![27, 91, 50, 49, 126],
-                KeyCode::F(11) => vec![27, 91, 50, 51, 126],
-                KeyCode::F(12) => vec![27, 91, 50, 52, 126],
-
-                KeyCode::Char(c) => {
-                    let mut buf = [0; 4];
-                    c.encode_utf8(&mut buf).as_bytes().to_vec()
-                }
-
-                _ => vec![],
-            }
-        };
+        let bytes_to_send = keys::encode_key(key, modifiers, screen.application_cursor());
 
         if !bytes_to_send.is_empty() {
             sender
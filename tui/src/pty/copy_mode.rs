@@ -0,0 +1,171 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use std::io::{self, Write};
+use tui_term::vt100::Screen;
+
+/// Keyboard-driven selection over the screen and its scrollback. Rows are absolute:
+/// 0 is the top row of the screen scrolled to the bottom, negative rows are in the scrollback
+pub struct CopyMode {
+    cursor: (i64, u16),
+    anchor: Option<(i64, u16)>,
+}
+
+/// vt100 does not expose the scrollback length, but clamps the offset to it
+fn scrollback_len(screen: &mut Screen) -> usize {
+    let offset = screen.scrollback();
+    screen.set_scrollback(usize::MAX);
+    let len = screen.scrollback();
+    screen.set_scrollback(offset);
+    len
+}
+
+impl CopyMode {
+    /// Starts at the cursor of the terminal, or at the top of the screen if the
+    /// terminal is scrolled back so far that its cursor is not visible
+    pub fn new(screen: &Screen) -> CopyMode {
+        // Позиция курсора не зависит от прокрутки, это уже абсолютная строка
+        let (row, col) = screen.cursor_position();
+        let (height, _) = screen.size();
+        let offset = screen.scrollback() as i64;
+        let cursor = if row as i64 + offset < height as i64 {
+            (row as i64, col)
+        } else {
+            (-offset, 0)
+        };
+        CopyMode {
+            cursor,
+            anchor: None,
+        }
+    }
+
+    /// Moves the cursor and scrolls the screen so that the cursor stays visible
+    pub fn move_cursor(&mut self, screen: &mut Screen, rows: i64, cols: i64) {
+        let (height, width) = screen.size();
+        let top = -(scrollback_len(screen) as i64);
+
+        let row = (self.cursor.0 + rows).clamp(top, height as i64 - 1);
+        let col = (self.cursor.1 as i64 + cols).clamp(0, width as i64 - 1);
+        self.cursor = (row, col as u16);
+
+        let offset = screen.scrollback() as i64;
+        if row + offset < 0 {
+            screen.set_scrollback((-row) as usize);
+        } else if row + offset >= height as i64 {
+            screen.set_scrollback((height as i64 - 1 - row).max(0) as usize);
+        }
+    }
+
+    pub fn toggle_selection(&mut self) {
+        self.anchor = match self.anchor {
+            Some(_) => None,
+            None => Some(self.cursor),
+        };
+    }
+
+    fn selection(&self) -> Option<((i64, u16), (i64, u16))> {
+        let anchor = self.anchor?;
+        Some((anchor.min(self.cursor), anchor.max(self.cursor)))
+    }
+
+    pub fn is_cursor(&self, row: i64, col: u16) -> bool {
+        self.cursor == (row, col)
+    }
+
+    pub fn is_selected(&self, row: i64, col: u16) -> bool {
+        self.selection()
+            .is_some_and(|(start, end)| start <= (row, col) && (row, col) <= end)
+    }
+
+    /// Text between the anchor and the cursor inclusive. Rows are read one by one,
+    /// scrolling the screen to each of them, so the selection may be taller than the screen
+    pub fn selected_text(&self, screen: &mut Screen) -> Option<String> {
+        let (start, end) = self.selection()?;
+        let (_, width) = screen.size();
+        let offset = screen.scrollback();
+
+        let mut text = String::new();
+        for row in start.0..=end.0 {
+            let visible_row = if row < 0 {
+                screen.set_scrollback((-row) as usize);
+                0
+            } else {
+                screen.set_scrollback(0);
+                row as u16
+            };
+
+            let from = if row == start.0 { start.1 } else { 0 };
+            let to = if row == end.0 { end.1 + 1 } else { width };
+            let line = screen.contents_between(visible_row, from, visible_row, to);
+            // Пробелы в конце перенесённой строки — настоящие, обрезается только конец строки
+            if screen.row_wrapped(visible_row) {
+                text.push_str(&line);
+            } else {
+                text.push_str(line.trim_end());
+                if row != end.0 {
+                    text.push('\n');
+                }
+            }
+        }
+
+        screen.set_scrollback(offset);
+        Some(text)
+    }
+}
+
+/// Asks the host terminal to put the text on the clipboard with OSC 52, which
+/// also works when git-trainer itself runs over SSH
+pub fn copy_to_clipboard(text: &str) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    write!(stdout, "\x1b]52;c;{}\x07", STANDARD.encode(text))?;
+    stdout.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tui_term::vt100::Parser;
+
+    fn parser_with_lines(count: usize) -> Parser {
+        let mut parser = Parser::new(5, 20, 100);
+        let lines: Vec<String> = (0..count).map(|i| format!("line{}", i)).collect();
+        parser.process(lines.join("\r\n").as_bytes());
+        parser
+    }
+
+    #[test]
+    fn starts_at_the_terminal_cursor() {
+        let parser = parser_with_lines(10);
+        let copy_mode = CopyMode::new(parser.screen());
+        assert!(copy_mode.is_cursor(4, 5));
+    }
+
+    #[test]
+    fn starts_on_the_visible_screen_when_scrolled_back() {
+        let mut parser = parser_with_lines(10);
+        parser.screen_mut().set_scrollback(2);
+        let mut copy_mode = CopyMode::new(parser.screen());
+        assert!(copy_mode.is_cursor(-2, 0));
+
+        copy_mode.toggle_selection();
+        copy_mode.move_cursor(parser.screen_mut(), 0, 4);
+        assert_eq!(
+            copy_mode.selected_text(parser.screen_mut()).as_deref(),
+            Some("line3")
+        );
+        assert_eq!(parser.screen().scrollback(), 2);
+    }
+
+    #[test]
+    fn keeps_spaces_at_a_wrap() {
+        let mut parser = Parser::new(5, 10, 0);
+        parser.process(b"hello     world  \r\nnext");
+        let copy_mode = CopyMode {
+            cursor: (2, 3),
+            anchor: Some((0, 0)),
+        };
+        assert_eq!(
+            copy_mode.selected_text(parser.screen_mut()).as_deref(),
+            Some("hello     world\nnext")
+        );
+    }
+}
@@ -0,0 +1,280 @@
+use crate::config::config;
+use crate::docker;
+use std::sync::{Arc, PoisonError, RwLock};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+/// Commits shown on the panel, older ones are cut off
+const MAX_COMMITS: usize = 200;
+
+/// Line of `git log --graph`: graph characters and, on commit lines, the commit itself
+pub struct GraphLine {
+    pub graph: String,
+    pub commit: Option<GraphCommit>,
+}
+
+pub struct GraphCommit {
+    pub hash: String,
+    /// Branches, tags and HEAD pointing to the commit, as `git log --format=%D` prints them
+    pub refs: String,
+    pub subject: String,
+}
+
+pub struct RepositorySummary {
+    /// None if HEAD is detached
+    pub branch: Option<String>,
+    /// None if there are no commits yet
+    pub head: Option<String>,
+    /// Commits ahead of and behind the upstream, if the branch has one
+    pub ahead_behind: Option<(u32, u32)>,
+    pub staged: usize,
+    pub unstaged: usize,
+    pub untracked: usize,
+    pub conflicts: usize,
+    pub graph: Vec<GraphLine>,
+}
+
+pub enum RepositoryView {
+    Loading,
+    /// The repository directory does not exist or is not a repository yet
+    Missing,
+    Ready(RepositorySummary),
+    Error(String),
+}
+
+/// Runs git in the container without taking locks, so that the queries
+/// never get in the way of the commands the student runs at the same time
+fn git_command(repository: &str, args: &str) -> String {
+    format!(
+        "git --no-optional-locks -c safe.directory=* -C {} {}",
+        repository, args
+    )
+}
+
+fn parse_status(output: &str) -> RepositorySummary {
+    let mut summary = RepositorySummary {
+        branch: None,
+        head: None,
+        ahead_behind: None,
+        staged: 0,
+        unstaged: 0,
+        untracked: 0,
+        conflicts: 0,
+        graph: Vec::new(),
+    };
+
+    for line in output.lines() {
+        if let Some(oid) = line.strip_prefix("# branch.oid ") {
+            if oid != "(initial)" {
+                summary.head = Some(oid.chars().take(7).collect());
+            }
+        } else if let Some(head) = line.strip_prefix("# branch.head ") {
+            if head != "(detached)" {
+                summary.branch = Some(head.to_string());
+            }
+        } else if let Some(ab) = line.strip_prefix("# branch.ab ") {
+            let mut counts = ab
+                .split_whitespace()
+                .map(|count| count.trim_start_matches(['+', '-']).parse().ok());
+            if let (Some(Some(ahead)), Some(Some(behind))) = (counts.next(), counts.next()) {
+                summary.ahead_behind = Some((ahead, behind));
+            }
+        } else if line.starts_with("? ") {
+            summary.untracked += 1;
+        } else if line.starts_with("u ") {
+            summary.conflicts += 1;
+        } else if line.starts_with("1 ") || line.starts_with("2 ") {
+            // Второе поле — XY: состояние в индексе и в рабочей копии, точка — без изменений
+            let xy = line.split(' ').nth(1).unwrap_or("..").as_bytes();
+            if xy.first().is_some_and(|&x| x != b'.') {
+                summary.staged += 1;
+            }
+            if xy.get(1).is_some_and(|&y| y != b'.') {
+                summary.unstaged += 1;
+            }
+        }
+    }
+    summary
+}
+
+fn parse_graph(output: &str) -> Vec<GraphLine> {
+    output
+        .lines()
+        .map(|line| {
+            let mut fields = line.splitn(3, '\t');
+            match (fields.next(), fields.next(), fields.next()) {
+                (Some(graph_and_hash), Some(refs), Some(subject)) => {
+                    let (graph, hash) = graph_and_hash
+                        .rsplit_once(' ')
+                        .unwrap_or(("", graph_and_hash));
+                    GraphLine {
+                        graph: format!("{} ", graph),
+                        commit: Some(GraphCommit {
+                            hash: hash.to_string(),
+                            refs: refs.to_string(),
+                            subject: subject.to_string(),
+                        }),
+                    }
+                }
+                _ => GraphLine {
+                    graph: line.to_string(),
+                    commit: None,
+                },
+            }
+        })
+        .collect()
+}
+
+pub async fn query_repository(
+    container_name: &str,
+    repository: &str,
+) -> Result<RepositoryView, bollard::errors::Error> {
+    let status = docker::exec_command(
+        container_name,
+        &git_command(repository, "status --porcelain=v2 --branch"),
+    )
+    .await?;
+    if status.exit_code != 0 {
+        return Ok(RepositoryView::Missing);
+    }
+    let mut summary = parse_status(&status.output);
+
+    // В репозитории без коммитов git log завершается с ошибкой, граф тогда пустой
+    if summary.head.is_some() {
+        let log = docker::exec_command(
+            container_name,
+            &git_command(
+                repository,
+                &format!(
+                    "log --graph --all --color=never --max-count={} --format=%h%x09%D%x09%s",
+                    MAX_COMMITS
+                ),
+            ),
+        )
+        .await?;
+        if log.exit_code == 0 {
+            summary.graph = parse_graph(&log.output);
+        }
+    }
+    Ok(RepositoryView::Ready(summary))
+}
+
+/// Queries the repository in the background while it is alive
+pub struct RepositoryPoller {
+    pub view: Arc<RwLock<RepositoryView>>,
+    handle: JoinHandle<()>,
+}
+
+impl RepositoryPoller {
+    pub fn spawn(container_name: String, repository: String) -> RepositoryPoller {
+        let view = Arc::new(RwLock::new(RepositoryView::Loading));
+        let handle = {
+            let view = view.clone();
+            tokio::spawn(async move {
+                let mut interval =
+                    tokio::time::interval(Duration::from_millis(config().intervals.git_graph_ms));
+                loop {
+                    interval.tick().await;
+                    let new_view = match query_repository(&container_name, &repository).await {
+                        Ok(new_view) => new_view,
+                        // Контейнер ставится на паузу во время проверки, старый граф ещё верен
+                        Err(_)
+                            if matches!(
+                                *view.read().unwrap_or_else(PoisonError::into_inner),
+                                RepositoryView::Ready(_)
+                            ) =>
+                        {
+                            continue;
+                        }
+                        Err(e) => RepositoryView::Error(e.to_string()),
+                    };
+                    *view.write().unwrap_or_else(PoisonError::into_inner) = new_view;
+                }
+            })
+        };
+        RepositoryPoller { view, handle }
+    }
+}
+
+impl Drop for RepositoryPoller {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_of_a_branch_with_changes() {
+        let summary = parse_status(
+            "# branch.oid 1234567890abcdef1234567890abcdef12345678\n\
+             # branch.head main\n\
+             # branch.upstream origin/main\n\
+             # branch.ab +2 -1\n\
+             1 M. N... 100644 100644 100644 aaaaaaa aaaaaaa staged.cpp\n\
+             1 .M N... 100644 100644 100644 aaaaaaa aaaaaaa unstaged.cpp\n\
+             1 MM N... 100644 100644 100644 aaaaaaa aaaaaaa both.cpp\n\
+             2 R. N... 100644 100644 100644 aaaaaaa aaaaaaa R100 new.cpp\told.cpp\n\
+             u UU N... 100644 100644 100644 100644 aaaaaaa aaaaaaa aaaaaaa conflict.cpp\n\
+             ? untracked.txt\n\
+             ? build/\n",
+        );
+        assert_eq!(summary.branch.as_deref(), Some("main"));
+        assert_eq!(summary.head.as_deref(), Some("1234567"));
+        assert_eq!(summary.ahead_behind, Some((2, 1)));
+        assert_eq!(summary.staged, 3);
+        assert_eq!(summary.unstaged, 2);
+        assert_eq!(summary.conflicts, 1);
+        assert_eq!(summary.untracked, 2);
+    }
+
+    #[test]
+    fn status_of_a_detached_head() {
+        let summary = parse_status(
+            "# branch.oid 1234567890abcdef1234567890abcdef12345678\n\
+             # branch.head (detached)\n",
+        );
+        assert_eq!(summary.branch, None);
+        assert_eq!(summary.head.as_deref(), Some("1234567"));
+        assert_eq!(summary.ahead_behind, None);
+    }
+
+    #[test]
+    fn status_without_commits() {
+        let summary = parse_status("# branch.oid (initial)\n# branch.head main\n? main.cpp\n");
+        assert_eq!(summary.branch.as_deref(), Some("main"));
+        assert_eq!(summary.head, None);
+        assert_eq!(summary.untracked, 1);
+        assert_eq!(summary.staged + summary.unstaged + summary.conflicts, 0);
+    }
+
+    #[test]
+    fn graph_with_continuation_lines() {
+        let graph = parse_graph(
+            "*   abc1234\tHEAD -> main\tMerge branch 'feature'\n\
+             |\\  \n\
+             | * def5678\tfeature\tAdd feature\n\
+             |/  \n\
+             * 0123456\t\tInitial commit\n",
+        );
+        assert_eq!(graph.len(), 5);
+
+        let merge = graph[0].commit.as_ref().unwrap();
+        assert_eq!(graph[0].graph, "*   ");
+        assert_eq!(merge.hash, "abc1234");
+        assert_eq!(merge.refs, "HEAD -> main");
+        assert_eq!(merge.subject, "Merge branch 'feature'");
+
+        assert!(graph[1].commit.is_none());
+        assert_eq!(graph[1].graph, "|\\  ");
+        assert_eq!(graph[2].graph, "| * ");
+        assert_eq!(graph[2].commit.as_ref().unwrap().hash, "def5678");
+        assert!(graph[3].commit.is_none());
+
+        let root = graph[4].commit.as_ref().unwrap();
+        assert_eq!(root.refs, "");
+        assert_eq!(root.subject, "Initial commit");
+    }
+}
@@ -0,0 +1,143 @@
+use crossterm::event::{
+    DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+    KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+use crossterm::execute;
+use ratatui::layout::Rect;
+use std::io;
+use tui_term::vt100::{MouseProtocolEncoding, MouseProtocolMode, Screen};
+
+/// Keeps bracketed paste enabled on the host terminal while the task terminal is open
+/// and captures the mouse only while the program in the container asked for it,
+/// so that the host terminal selection keeps working otherwise
+pub struct HostInputModes {
+    mouse_captured: bool,
+}
+
+impl HostInputModes {
+    pub fn enable() -> io::Result<HostInputModes> {
+        execute!(io::stdout(), EnableBracketedPaste)?;
+        Ok(HostInputModes {
+            mouse_captured: false,
+        })
+    }
+
+    pub fn sync_mouse_capture(&mut self, screen: &Screen) -> io::Result<()> {
+        let wanted = screen.mouse_protocol_mode() != MouseProtocolMode::None;
+        if wanted == self.mouse_captured {
+            return Ok(());
+        }
+
+        if wanted {
+            execute!(io::stdout(), EnableMouseCapture)?;
+        } else {
+            execute!(io::stdout(), DisableMouseCapture)?;
+        }
+        self.mouse_captured = wanted;
+        Ok(())
+    }
+}
+
+impl Drop for HostInputModes {
+    fn drop(&mut self) {
+        if self.mouse_captured {
+            let _ = execute!(io::stdout(), DisableMouseCapture);
+        }
+        let _ = execute!(io::stdout(), DisableBracketedPaste);
+    }
+}
+
+/// Pasted text is wrapped into ESC [200~ … ESC [201~ if the program enabled
+/// bracketed paste. Line breaks are sent as carriage returns, like terminals do
+pub fn encode_paste(text: &str, bracketed: bool) -> Vec<u8> {
+    let text = text.replace("\r\n", "\r").replace('\n', "\r");
+    if bracketed {
+        // Программа не должна получить конец вставки раньше времени
+        let text = text.replace("\x1b[201~", "");
+        format!("\x1b[200~{}\x1b[201~", text).into_bytes()
+    } else {
+        text.into_bytes()
+    }
+}
+
+/// Encodes a host mouse event for the program in the container according to its
+/// mouse mode. `area` is where the screen is drawn, events outside of it are dropped
+pub fn encode_mouse(event: &MouseEvent, screen: &Screen, area: Rect) -> Option<Vec<u8>> {
+    let mode = screen.mouse_protocol_mode();
+    if mode == MouseProtocolMode::None || !area.contains((event.column, event.row).into()) {
+        return None;
+    }
+    let col = event.column - area.x + 1;
+    let row = event.row - area.y + 1;
+
+    let button_code = |button: MouseButton| -> u16 {
+        match button {
+            MouseButton::Left => 0,
+            MouseButton::Middle => 1,
+            MouseButton::Right => 2,
+        }
+    };
+    let (mut code, release) = match event.kind {
+        MouseEventKind::Down(button) => (button_code(button), false),
+        MouseEventKind::Up(button) => {
+            if mode == MouseProtocolMode::Press {
+                return None;
+            }
+            (button_code(button), true)
+        }
+        MouseEventKind::Drag(button) => {
+            if mode != MouseProtocolMode::ButtonMotion && mode != MouseProtocolMode::AnyMotion {
+                return None;
+            }
+            (button_code(button) + 32, false)
+        }
+        MouseEventKind::Moved => {
+            if mode != MouseProtocolMode::AnyMotion {
+                return None;
+            }
+            (3 + 32, false)
+        }
+        MouseEventKind::ScrollUp => (64, false),
+        MouseEventKind::ScrollDown => (65, false),
+        MouseEventKind::ScrollLeft => (66, false),
+        MouseEventKind::ScrollRight => (67, false),
+    };
+
+    if mode != MouseProtocolMode::Press {
+        if event.modifiers.contains(KeyModifiers::SHIFT) {
+            code += 4;
+        }
+        if event.modifiers.contains(KeyModifiers::ALT) {
+            code += 8;
+        }
+        if event.modifiers.contains(KeyModifiers::CONTROL) {
+            code += 16;
+        }
+    }
+
+    match screen.mouse_protocol_encoding() {
+        MouseProtocolEncoding::Sgr => {
+            let end = if release { 'm' } else { 'M' };
+            Some(format!("\x1b[<{};{};{}{}", code, col, row, end).into_bytes())
+        }
+        encoding => {
+            // В старых кодировках отпускание не говорит, какая кнопка отпущена
+            if release {
+                code = 3 | (code & !3);
+            }
+            let mut bytes = b"\x1b[M".to_vec();
+            for value in [code, col, row] {
+                let value = u32::from(value) + 32;
+                if encoding == MouseProtocolEncoding::Utf8 {
+                    let mut buf = [0; 4];
+                    bytes
+                        .extend_from_slice(char::from_u32(value)?.encode_utf8(&mut buf).as_bytes());
+                } else {
+                    // Координаты больше 223 не помещаются в один байт
+                    bytes.push(u8::try_from(value).ok()?);
+                }
+            }
+            Some(bytes)
+        }
+    }
+}
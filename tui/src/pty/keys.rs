@@ -0,0 +1,362 @@
+use crossterm::event::{KeyCode, KeyModifiers};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// xterm modifier parameter: 1 + Shift + 2·Alt + 4·Ctrl, or None without modifiers
+fn modifier_param(modifiers: KeyModifiers) -> Option<u8> {
+    let mut param = 1;
+    if modifiers.contains(KeyModifiers::SHIFT) {
+        param += 1;
+    }
+    if modifiers.contains(KeyModifiers::ALT) {
+        param += 2;
+    }
+    if modifiers.contains(KeyModifiers::CONTROL) {
+        param += 4;
+    }
+    (param > 1).then_some(param)
+}
+
+/// Keys sent as `ESC [ <letter>` or, in application cursor mode, `ESC O <letter>`.
+/// With modifiers they become `ESC [ 1 ; <m> <letter>`
+fn cursor_key(letter: u8, modifiers: KeyModifiers, application_cursor: bool) -> Vec<u8> {
+    match modifier_param(modifiers) {
+        Some(param) => format!("\x1b[1;{}{}", param, letter as char).into_bytes(),
+        None if application_cursor => vec![0x1b, b'O', letter],
+        None => vec![0x1b, b'[', letter],
+    }
+}
+
+/// F1–F4 are sent as `ESC O <letter>` regardless of the cursor mode
+fn ss3_key(letter: u8, modifiers: KeyModifiers) -> Vec<u8> {
+    match modifier_param(modifiers) {
+        Some(param) => format!("\x1b[1;{}{}", param, letter as char).into_bytes(),
+        None => vec![0x1b, b'O', letter],
+    }
+}
+
+/// Keys sent as `ESC [ <number> ~`, with modifiers `ESC [ <number> ; <m> ~`
+fn tilde_key(number: u8, modifiers: KeyModifiers) -> Vec<u8> {
+    match modifier_param(modifiers) {
+        Some(param) => format!("\x1b[{};{}~", number, param).into_bytes(),
+        None => format!("\x1b[{}~", number).into_bytes(),
+    }
+}
+
+/// Control character for Ctrl+<c>, as xterm produces it
+fn control_byte(c: char) -> Option<u8> {
+    match c {
+        'a'..='z' | 'A'..='Z' => Some(c.to_ascii_lowercase() as u8 & 0x1F),
+        '@' | ' ' | '2' => Some(0),
+        '[' | '3' => Some(27),
+        '\\' | '4' => Some(28),
+        ']' | '5' => Some(29),
+        '^' | '6' => Some(30),
+        '_' | '/' | '7' => Some(31),
+        '?' | '8' => Some(127),
+        _ => None,
+    }
+}
+
+/// Prepends ESC for Alt, like xterm with `metaSendsEscape`
+fn with_alt(bytes: Vec<u8>, modifiers: KeyModifiers) -> Vec<u8> {
+    if modifiers.contains(KeyModifiers::ALT) {
+        let mut prefixed = vec![0x1b];
+        prefixed.extend(bytes);
+        prefixed
+    } else {
+        bytes
+    }
+}
+
+/// Bytes an xterm would send for the key. `application_cursor` is the DECCKM mode
+/// of the screen. Keys without an xterm encoding produce nothing
+pub fn encode_key(code: KeyCode, modifiers: KeyModifiers, application_cursor: bool) -> Vec<u8> {
+    match code {
+        KeyCode::Char(c) => {
+            let bytes = if modifiers.contains(KeyModifiers::CONTROL) {
+                match control_byte(c) {
+                    Some(byte) => vec![byte],
+                    None => return vec![],
+                }
+            } else {
+                let mut buf = [0; 4];
+                c.encode_utf8(&mut buf).as_bytes().to_vec()
+            };
+            with_alt(bytes, modifiers)
+        }
+        KeyCode::Enter => with_alt(vec![b'\r'], modifiers),
+        KeyCode::Tab => with_alt(vec![b'\t'], modifiers),
+        KeyCode::BackTab => with_alt(b"\x1b[Z".to_vec(), modifiers),
+        KeyCode::Backspace => {
+            // xterm отправляет ^H на Ctrl+Backspace, чтобы его можно было отличить
+            let byte = if modifiers.contains(KeyModifiers::CONTROL) {
+                0x08
+            } else {
+                0x7F
+            };
+            with_alt(vec![byte], modifiers)
+        }
+        KeyCode::Esc => with_alt(vec![0x1b], modifiers),
+
+        KeyCode::Up => cursor_key(b'A', modifiers, application_cursor),
+        KeyCode::Down => cursor_key(b'B', modifiers, application_cursor),
+        KeyCode::Right => cursor_key(b'C', modifiers, application_cursor),
+        KeyCode::Left => cursor_key(b'D', modifiers, application_cursor),
+        KeyCode::Home => cursor_key(b'H', modifiers, application_cursor),
+        KeyCode::End => cursor_key(b'F', modifiers, application_cursor),
+
+        KeyCode::Insert => tilde_key(2, modifiers),
+        KeyCode::Delete => tilde_key(3, modifiers),
+        KeyCode::PageUp => tilde_key(5, modifiers),
+        KeyCode::PageDown => tilde_key(6, modifiers),
+
+        KeyCode::F(n @ 1..=4) => ss3_key(b'P' + (n - 1), modifiers),
+        KeyCode::F(5) => tilde_key(15, modifiers),
+        KeyCode::F(n @ 6..=10) => tilde_key(n + 11, modifiers),
+        KeyCode::F(n @ 11..=12) => tilde_key(n + 12, modifiers),
+
+        _ => vec![],
+    }
+}
+
+/// Key combination from the config, written like `ctrl-b`, `alt-g` or `f12`
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    /// Shift is ignored, terminals do not report it for characters consistently
+    pub fn matches(&self, code: KeyCode, modifiers: KeyModifiers) -> bool {
+        let relevant = KeyModifiers::CONTROL | KeyModifiers::ALT;
+        let code = match code {
+            KeyCode::Char(c) => KeyCode::Char(c.to_ascii_lowercase()),
+            code => code,
+        };
+        code == self.code && modifiers & relevant == self.modifiers
+    }
+}
+
+impl TryFrom<String> for KeyBinding {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let invalid = || format!("invalid key \"{}\", expected e.g. ctrl-b or f12", value);
+        let lowercase = value.to_lowercase();
+        let (modifier_names, key) = match lowercase.rsplit_once('-') {
+            // "ctrl--" — это Ctrl и минус
+            Some((rest, "")) => (rest.strip_suffix('-').ok_or_else(invalid)?, "-"),
+            Some((modifier_names, key)) => (modifier_names, key),
+            None => ("", lowercase.as_str()),
+        };
+
+        let mut modifiers = KeyModifiers::NONE;
+        for name in modifier_names.split('-').filter(|name| !name.is_empty()) {
+            modifiers |= match name {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" | "meta" => KeyModifiers::ALT,
+                _ => return Err(invalid()),
+            };
+        }
+
+        let mut chars = key.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match key.strip_prefix('f').and_then(|n| n.parse().ok()) {
+                Some(n @ 1..=12) => KeyCode::F(n),
+                _ => return Err(invalid()),
+            },
+        };
+        Ok(KeyBinding { code, modifiers })
+    }
+}
+
+impl From<KeyBinding> for String {
+    fn from(binding: KeyBinding) -> Self {
+        let mut parts = Vec::new();
+        if binding.modifiers.contains(KeyModifiers::CONTROL) {
+            parts.push("ctrl".to_string());
+        }
+        if binding.modifiers.contains(KeyModifiers::ALT) {
+            parts.push("alt".to_string());
+        }
+        parts.push(match binding.code {
+            KeyCode::F(n) => format!("f{}", n),
+            KeyCode::Char(c) => c.to_string(),
+            _ => String::new(),
+        });
+        parts.join("-")
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    /// As shown in the interface, e.g. `Ctrl+B`
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt+")?;
+        }
+        match self.code {
+            KeyCode::F(n) => write!(f, "F{}", n),
+            KeyCode::Char(c) => write!(f, "{}", c.to_ascii_uppercase()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NONE: KeyModifiers = KeyModifiers::NONE;
+    const SHIFT: KeyModifiers = KeyModifiers::SHIFT;
+    const ALT: KeyModifiers = KeyModifiers::ALT;
+    const CTRL: KeyModifiers = KeyModifiers::CONTROL;
+
+    fn encode(code: KeyCode, modifiers: KeyModifiers) -> Vec<u8> {
+        encode_key(code, modifiers, false)
+    }
+
+    #[test]
+    fn plain_characters() {
+        assert_eq!(encode(KeyCode::Char('a'), NONE), b"a");
+        assert_eq!(encode(KeyCode::Char('A'), SHIFT), b"A");
+        assert_eq!(encode(KeyCode::Char('ё'), NONE), "ё".as_bytes());
+    }
+
+    #[test]
+    fn control_characters() {
+        assert_eq!(encode(KeyCode::Char('c'), CTRL), b"\x03");
+        assert_eq!(encode(KeyCode::Char('C'), CTRL | SHIFT), b"\x03");
+        assert_eq!(encode(KeyCode::Char(' '), CTRL), b"\x00");
+        assert_eq!(encode(KeyCode::Char('@'), CTRL), b"\x00");
+        assert_eq!(encode(KeyCode::Char('['), CTRL), b"\x1b");
+        assert_eq!(encode(KeyCode::Char('\\'), CTRL), b"\x1c");
+        assert_eq!(encode(KeyCode::Char(']'), CTRL), b"\x1d");
+        assert_eq!(encode(KeyCode::Char('^'), CTRL), b"\x1e");
+        assert_eq!(encode(KeyCode::Char('_'), CTRL), b"\x1f");
+        assert_eq!(encode(KeyCode::Char('/'), CTRL), b"\x1f");
+        assert_eq!(encode(KeyCode::Char('?'), CTRL), b"\x7f");
+        assert_eq!(encode(KeyCode::Char('1'), CTRL), b"");
+    }
+
+    #[test]
+    fn alt_prefixes_escape() {
+        assert_eq!(encode(KeyCode::Char('b'), ALT), b"\x1bb");
+        assert_eq!(encode(KeyCode::Char('x'), CTRL | ALT), b"\x1b\x18");
+        assert_eq!(encode(KeyCode::Enter, ALT), b"\x1b\r");
+        assert_eq!(encode(KeyCode::Backspace, ALT), b"\x1b\x7f");
+    }
+
+    #[test]
+    fn editing_keys() {
+        assert_eq!(encode(KeyCode::Enter, NONE), b"\r");
+        assert_eq!(encode(KeyCode::Tab, NONE), b"\t");
+        assert_eq!(encode(KeyCode::BackTab, SHIFT), b"\x1b[Z");
+        assert_eq!(encode(KeyCode::Backspace, NONE), b"\x7f");
+        assert_eq!(encode(KeyCode::Backspace, CTRL), b"\x08");
+        assert_eq!(encode(KeyCode::Esc, NONE), b"\x1b");
+    }
+
+    #[test]
+    fn cursor_keys_follow_cursor_mode() {
+        assert_eq!(encode_key(KeyCode::Up, NONE, false), b"\x1b[A");
+        assert_eq!(encode_key(KeyCode::Down, NONE, false), b"\x1b[B");
+        assert_eq!(encode_key(KeyCode::Right, NONE, false), b"\x1b[C");
+        assert_eq!(encode_key(KeyCode::Left, NONE, false), b"\x1b[D");
+        assert_eq!(encode_key(KeyCode::Home, NONE, false), b"\x1b[H");
+        assert_eq!(encode_key(KeyCode::End, NONE, false), b"\x1b[F");
+
+        assert_eq!(encode_key(KeyCode::Up, NONE, true), b"\x1bOA");
+        assert_eq!(encode_key(KeyCode::Left, NONE, true), b"\x1bOD");
+        assert_eq!(encode_key(KeyCode::Home, NONE, true), b"\x1bOH");
+        assert_eq!(encode_key(KeyCode::End, NONE, true), b"\x1bOF");
+    }
+
+    #[test]
+    fn cursor_keys_with_modifiers() {
+        assert_eq!(encode(KeyCode::Up, SHIFT), b"\x1b[1;2A");
+        assert_eq!(encode(KeyCode::Right, ALT), b"\x1b[1;3C");
+        assert_eq!(encode(KeyCode::Left, CTRL), b"\x1b[1;5D");
+        assert_eq!(encode(KeyCode::Right, CTRL | SHIFT), b"\x1b[1;6C");
+        assert_eq!(encode(KeyCode::Down, CTRL | ALT | SHIFT), b"\x1b[1;8B");
+        assert_eq!(encode(KeyCode::Home, SHIFT), b"\x1b[1;2H");
+        assert_eq!(encode(KeyCode::End, CTRL), b"\x1b[1;5F");
+        // С модификаторами режим курсора не учитывается
+        assert_eq!(encode_key(KeyCode::Left, CTRL, true), b"\x1b[1;5D");
+    }
+
+    #[test]
+    fn tilde_keys() {
+        assert_eq!(encode(KeyCode::Insert, NONE), b"\x1b[2~");
+        assert_eq!(encode(KeyCode::Delete, NONE), b"\x1b[3~");
+        assert_eq!(encode(KeyCode::PageUp, NONE), b"\x1b[5~");
+        assert_eq!(encode(KeyCode::PageDown, NONE), b"\x1b[6~");
+        assert_eq!(encode(KeyCode::Delete, CTRL), b"\x1b[3;5~");
+        assert_eq!(encode(KeyCode::PageUp, ALT), b"\x1b[5;3~");
+    }
+
+    #[test]
+    fn function_keys() {
+        let expected: [&[u8]; 12] = [
+            b"\x1bOP",
+            b"\x1bOQ",
+            b"\x1bOR",
+            b"\x1bOS",
+            b"\x1b[15~",
+            b"\x1b[17~",
+            b"\x1b[18~",
+            b"\x1b[19~",
+            b"\x1b[20~",
+            b"\x1b[21~",
+            b"\x1b[23~",
+            b"\x1b[24~",
+        ];
+        for (n, bytes) in (1..=12).zip(expected) {
+            assert_eq!(encode(KeyCode::F(n), NONE), bytes, "F{n}");
+            // Режим курсора не влияет на функциональные клавиши
+            assert_eq!(encode_key(KeyCode::F(n), NONE, true), bytes, "F{n}");
+        }
+        assert_eq!(encode(KeyCode::F(13), NONE), b"");
+    }
+
+    #[test]
+    fn function_keys_with_modifiers() {
+        assert_eq!(encode(KeyCode::F(1), SHIFT), b"\x1b[1;2P");
+        assert_eq!(encode(KeyCode::F(4), CTRL), b"\x1b[1;5S");
+        assert_eq!(encode(KeyCode::F(5), SHIFT), b"\x1b[15;2~");
+        assert_eq!(encode(KeyCode::F(12), CTRL | ALT), b"\x1b[24;7~");
+    }
+
+    fn binding(value: &str) -> Result<KeyBinding, String> {
+        KeyBinding::try_from(value.to_string())
+    }
+
+    #[test]
+    fn key_bindings() {
+        let prefix = binding("ctrl-b").unwrap();
+        assert_eq!(prefix.code, KeyCode::Char('b'));
+        assert_eq!(prefix.modifiers, CTRL);
+        assert!(prefix.matches(KeyCode::Char('b'), CTRL));
+        assert!(prefix.matches(KeyCode::Char('B'), CTRL | SHIFT));
+        assert!(!prefix.matches(KeyCode::Char('b'), NONE));
+        assert!(!prefix.matches(KeyCode::Char('b'), CTRL | ALT));
+        assert_eq!(prefix.to_string(), "Ctrl+B");
+        assert_eq!(String::from(prefix), "ctrl-b");
+
+        assert_eq!(binding("F12").unwrap().code, KeyCode::F(12));
+        assert_eq!(binding("Ctrl-Alt-a").unwrap().modifiers, CTRL | ALT);
+        assert_eq!(binding("ctrl--").unwrap().code, KeyCode::Char('-'));
+        assert_eq!(String::from(binding("alt-f3").unwrap()), "alt-f3");
+
+        assert!(binding("").is_err());
+        assert!(binding("f13").is_err());
+        assert!(binding("super-b").is_err());
+        assert!(binding("ctrl-bb").is_err());
+    }
+}
@@ -1,2 +1,6 @@
 pub mod app;
+pub mod copy_mode;
+pub mod git_graph;
+pub mod input;
+pub mod keys;
 pub mod ui;
@@ -1,15 +1,24 @@
 use crate::Frame;
-use crate::app::{App, VERSION};
+use crate::app::{App, GradedAttempt, SidePanels, VERSION};
+use crate::config::config;
+use crate::db::TestResult;
 use crate::docker::resize_container;
-use crate::docker::{self, ensure_task_container_created};
+use crate::docker::{self, TaskContainer};
+use crate::error::AppError;
+use crate::events::{EventListener, Request, Response};
+use crate::locale::{tr, tr_fmt};
+use crate::manifest;
+use crate::pty::copy_mode::CopyMode;
+use crate::pty::git_graph::{RepositoryPoller, RepositorySummary, RepositoryView};
+use crate::pty::input::{self, HostInputModes};
+use crate::recording::Recorder;
 use crossterm::event;
-use crossterm::event::{Event, KeyEventKind};
-use futures_util::FutureExt;
-use ratatui::layout::{Alignment, Constraint};
+use crossterm::event::{Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Alignment, Constraint, Rect};
 use ratatui::prelude::{Direction, Layout};
-use ratatui::style::{Modifier, Style, Stylize};
-use ratatui::text::Line;
-use ratatui::widgets::{Block, Borders, Paragraph};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
 use tui_term::{vt100, widget::PseudoTerminal};
 use vt100::Screen;
 
@@ -18,13 +27,15 @@ use ratatui::DefaultTerminal;
 use std::time::Duration;
 use std::{
     io,
-    sync::{Arc, RwLock},
+    sync::{Arc, Mutex, PoisonError, RwLock},
 };
 
 use bollard::container::LogOutput;
 use futures::StreamExt;
 use thiserror::Error;
 use tokio::io::AsyncWriteExt;
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
 
 #[derive(Debug)]
 struct Size {
@@ -33,6 +44,7 @@ struct Size {
 }
 
 #[derive(Debug, Error)]
+#[allow(clippy::enum_variant_names)]
 pub enum PreparePtyError {
     #[error("While working with Docker: {0}")]
     DockerError(#[from] bollard::errors::Error),
@@ -48,6 +60,7 @@ pub enum PreparePtyError {
 }
 
 #[derive(Debug, Error)]
+#[allow(clippy::enum_variant_names)]
 pub enum RunPtyError {
     #[error("While working with Docker: {0}")]
     DockerError(#[from] bollard::errors::Error),
@@ -65,69 +78,306 @@ pub enum RunPtyError {
 pub enum PtyExitStatus {
     Exit,
     RestartTask,
+    /// The container was not started, it was created for another version of the task
+    ContainerOutdated,
+}
+
+/// Grading started by `git-trainer submit`, the CLI waits for its result on `reply`
+struct Grading {
+    handle: JoinHandle<Result<GradedAttempt, AppError>>,
+    reply: oneshot::Sender<Response>,
+}
+
+fn pty_layout(area: Rect) -> [Rect; 3] {
+    Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Min(1),
+            Constraint::Length(1),
+        ])
+        .areas(area)
+}
+
+/// Areas of the task pane, the terminal and the repository panel in the middle of the screen
+struct PanelAreas {
+    task_description: Option<Rect>,
+    terminal: Rect,
+    git_graph: Option<Rect>,
+}
+
+fn split_side_panels(area: Rect, panels: SidePanels) -> PanelAreas {
+    let mut constraints = Vec::new();
+    if panels.task_description {
+        constraints.push(Constraint::Percentage(30));
+    }
+    constraints.push(Constraint::Fill(1));
+    if panels.git_graph {
+        constraints.push(Constraint::Percentage(35));
+    }
+
+    let chunks = Layout::horizontal(constraints).split(area);
+    let mut chunks = chunks.iter().copied();
+    PanelAreas {
+        task_description: panels.task_description.then(|| chunks.next()).flatten(),
+        terminal: chunks.next().unwrap_or(area),
+        git_graph: panels.git_graph.then(|| chunks.next()).flatten(),
+    }
+}
+
+/// Where the screen of the container is drawn inside the whole terminal area
+fn terminal_area(area: Rect, panels: SidePanels) -> Rect {
+    let terminal = split_side_panels(pty_layout(area)[1], panels).terminal;
+    Block::default().borders(Borders::ALL).inner(terminal)
+}
+
+fn pty_size(area: Rect, panels: SidePanels) -> Size {
+    let area = terminal_area(area, panels);
+    Size {
+        rows: area.height,
+        cols: area.width,
+    }
+}
+
+fn repository_lines(summary: &RepositorySummary) -> Vec<Line<'_>> {
+    let mut lines = Vec::new();
+    lines.push(match &summary.branch {
+        Some(branch) => Line::from(vec![
+            tr("git_panel.branch").into(),
+            branch.as_str().light_green().bold(),
+        ]),
+        None => Line::from(tr("git_panel.detached").light_red()),
+    });
+    lines.push(match &summary.head {
+        Some(head) => Line::from(vec!["HEAD: ".into(), head.as_str().light_yellow()]),
+        None => Line::from(tr("git_panel.no_commits").dark_gray()),
+    });
+    if let Some((ahead, behind)) = summary.ahead_behind {
+        lines.push(Line::from(tr_fmt(
+            "git_panel.upstream",
+            &[("ahead", &ahead), ("behind", &behind)],
+        )));
+    }
+    lines.push(Line::from(tr_fmt(
+        "git_panel.changes",
+        &[
+            ("staged", &summary.staged),
+            ("unstaged", &summary.unstaged),
+            ("untracked", &summary.untracked),
+        ],
+    )));
+    if summary.conflicts > 0 {
+        lines.push(Line::from(
+            tr_fmt("git_panel.conflicts", &[("count", &summary.conflicts)]).light_red(),
+        ));
+    }
+    lines.push(Line::default());
+
+    for graph_line in &summary.graph {
+        let mut spans = vec![Span::from(graph_line.graph.as_str()).light_blue()];
+        if let Some(commit) = &graph_line.commit {
+            spans.push(commit.hash.as_str().light_yellow());
+            if !commit.refs.is_empty() {
+                spans.push(format!(" ({})", commit.refs).light_cyan().bold());
+            }
+            spans.push(format!(" {}", commit.subject).into());
+        }
+        lines.push(Line::from(spans));
+    }
+    lines
+}
+
+fn render_git_panel(frame: &mut Frame, view: &RepositoryView, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(tr("git_panel.title").bold());
+    let lines = match view {
+        RepositoryView::Loading => vec![Line::from(tr("git_panel.loading").dark_gray())],
+        RepositoryView::Missing => vec![Line::from(tr("git_panel.missing").dark_gray())],
+        RepositoryView::Error(e) => vec![Line::from(
+            tr_fmt("git_panel.error", &[("error", e)]).light_red(),
+        )],
+        RepositoryView::Ready(summary) => repository_lines(summary),
+    };
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// Highlights the selection and the cursor of the copy mode over the rendered screen
+fn render_copy_mode(frame: &mut Frame, copy_mode: &CopyMode, screen: &Screen, area: Rect) {
+    let (rows, cols) = screen.size();
+    let offset = screen.scrollback() as i64;
+    let buffer = frame.buffer_mut();
+
+    for visible_row in 0..rows.min(area.height) {
+        let row = visible_row as i64 - offset;
+        for col in 0..cols.min(area.width) {
+            let position = (area.x + col, area.y + visible_row);
+            if copy_mode.is_cursor(row, col) {
+                buffer[position]
+                    .set_style(Style::default().fg(Color::Black).bg(Color::LightYellow));
+            } else if copy_mode.is_selected(row, col) {
+                buffer[position].set_style(Style::default().add_modifier(Modifier::REVERSED));
+            }
+        }
+    }
 }
 
 impl App {
-    pub fn render_pty(&mut self, frame: &mut Frame, screen: &Screen) {
+    pub fn render_pty(
+        &mut self,
+        frame: &mut Frame,
+        screen: &Screen,
+        repository: Option<&RepositoryView>,
+        grading: bool,
+    ) {
         let title = Line::from(format!("git-trainer v{}", VERSION).bold()).centered();
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints(
-                [
-                    Constraint::Length(1),
-                    Constraint::Min(1),
-                    Constraint::Length(1),
-                ]
-                .as_ref(),
-            )
-            .split(frame.area());
+        let chunks = pty_layout(frame.area());
+        let panels = split_side_panels(chunks[1], self.side_panels);
         let block = Block::default()
             .borders(Borders::ALL)
             .style(Style::default().add_modifier(Modifier::BOLD));
+        let terminal_area = block.inner(panels.terminal);
         let pseudo_term = PseudoTerminal::new(screen).block(block);
-        let explanation =
-            "Напишите команду exit для выхода и git-trainer для помощи и сдачи заданий".to_string();
+        let explanation = if grading {
+            tr("terminal.footer_grading").to_string()
+        } else if self.copy_mode.is_some() {
+            tr("terminal.footer_copy_mode").to_string()
+        } else if self.prefix_pressed {
+            tr("terminal.footer_prefix").to_string()
+        } else if screen.scrollback() > 0 {
+            tr_fmt(
+                "terminal.footer_scrolled",
+                &[("lines", &screen.scrollback())],
+            )
+        } else {
+            tr_fmt("terminal.footer", &[("prefix", &config().ui.prefix_key)])
+        };
         let explanation = Paragraph::new(explanation)
             .style(Style::default())
             .alignment(Alignment::Center);
         frame.render_widget(title, chunks[0]);
-        frame.render_widget(pseudo_term, chunks[1]);
+        frame.render_widget(pseudo_term, panels.terminal);
         frame.render_widget(explanation, chunks[2]);
 
+        if let Some(area) = panels.task_description {
+            self.render_task_pane(frame, area);
+        }
+        if let (Some(area), Some(repository)) = (panels.git_graph, repository) {
+            render_git_panel(frame, repository, area);
+        }
+
+        if let Some(copy_mode) = &self.copy_mode {
+            render_copy_mode(frame, copy_mode, screen, terminal_area);
+        }
+
         if let Some(popup) = &self.active_popup {
             popup.render(frame);
         }
     }
 
+    /// Statement of the task under cursor followed by the results of its last attempt
+    fn render_task_pane(&self, frame: &mut Frame, area: Rect) {
+        let task = self.task_under_cursor();
+        let mut lines: Vec<Line> = task
+            .extended_description
+            .lines()
+            .map(|line| Line::from(line.to_string()))
+            .collect();
+        lines.push(Line::default());
+
+        match task.attempts.first() {
+            None => lines.push(Line::from(tr("task_pane.no_attempts").dark_gray())),
+            Some(attempt) => {
+                let timestamp = attempt.timestamp.as_deref().unwrap_or("?");
+                lines.push(Line::from(
+                    tr_fmt("task_pane.last_attempt", &[("timestamp", &timestamp)]).bold(),
+                ));
+
+                let tests = &attempt.tests;
+                let passed_count = tests
+                    .iter()
+                    .filter(|t| t.result == TestResult::Passed)
+                    .count();
+                lines.push(Line::from(tr_fmt(
+                    "task_pane.tests_passed",
+                    &[("passed", &passed_count), ("total", &tests.len())],
+                )));
+                for test in tests {
+                    let (mark, color) = match test.result {
+                        TestResult::Passed => ("✔", Color::LightGreen),
+                        TestResult::Failed => ("✘", Color::Red),
+                        TestResult::NotExecuted => ("–", Color::DarkGray),
+                        TestResult::TimedOut => ("⌛", Color::Yellow),
+                    };
+                    lines.push(Line::from(
+                        format!("{} {}", mark, test.description).fg(color),
+                    ));
+                    if test.result != TestResult::Passed
+                        && let Some(hint) = &test.hint
+                    {
+                        lines.push(Line::from(
+                            format!("  {}", tr_fmt("task_pane.hint", &[("hint", hint)]))
+                                .dark_gray(),
+                        ));
+                    }
+                }
+            }
+        }
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(task.name.as_str().bold());
+        let pane = Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap { trim: false });
+        frame.render_widget(pane, area);
+    }
+
     pub async fn prepare_pty_bollard(
         &mut self,
         terminal: &mut DefaultTerminal,
     ) -> Result<PtyExitStatus, PreparePtyError> {
         let task = self.task_under_cursor();
         let mut handles = Vec::new();
-        let size = Size {
-            rows: terminal.size()?.height - 4,
-            cols: terminal.size()?.width - 2,
-        };
+        let terminal_size = terminal.size()?;
+        let size = pty_size(
+            Rect::new(0, 0, terminal_size.width, terminal_size.height),
+            self.side_panels,
+        );
 
-        ensure_task_container_created(task).await?;
         let container_name = task.container_name.clone();
-        let res = docker::attach_container(task).await?;
+        let check_version = !self.kept_outdated_containers.contains(&container_name);
+        let mut listener = EventListener::bind(&container_name)?;
+        let res = match docker::ensure_task_container_running(task, check_version).await? {
+            TaskContainer::Running(res) => res,
+            TaskContainer::Outdated => return Ok(PtyExitStatus::ContainerOutdated),
+        };
 
         let mut output_stream = res.output;
         let mut input = res.input;
-        docker::start_container(task).await?;
 
         docker::resize_container(container_name.clone(), size.rows as i32, size.cols as i32)
             .await?;
 
-        let parser = Arc::new(RwLock::new(vt100::Parser::new(size.rows, size.cols, 0)));
+        let parser = Arc::new(RwLock::new(vt100::Parser::new(
+            size.rows,
+            size.cols,
+            self.scrollback,
+        )));
+
+        self.recorder = if self.record_sessions {
+            let recorder = Recorder::create(&container_name, size.cols, size.rows)?;
+            Some(Arc::new(Mutex::new(recorder)))
+        } else {
+            None
+        };
 
         let (tx, mut rx) = tokio::sync::mpsc::channel::<Bytes>(1024);
         let (exit_tx, exit_rx) = std::sync::mpsc::channel::<()>();
 
         {
             let parser = parser.clone();
+            let recorder = self.recorder.clone();
             let exit_tx = exit_tx.clone();
             let handle = tokio::spawn(async move {
                 use std::io::Write;
@@ -147,6 +397,12 @@ impl App {
                             {
                                 let _ = p.write_all(bytes);
                             }
+                            if let Some(recorder) = &recorder {
+                                let _ = recorder
+                                    .lock()
+                                    .unwrap_or_else(PoisonError::into_inner)
+                                    .output(bytes);
+                            }
                         }
                         Err(e) => {
                             eprintln!("docker output error: {e}");
@@ -173,32 +429,86 @@ impl App {
         });
         handles.push(writer_handle);
         let exit_status = self
-            .run_pty_bollard(terminal, parser, tx, exit_rx, container_name)
+            .run_pty_bollard(terminal, parser, tx, exit_rx, &mut listener, container_name)
             .await?;
 
         for handle in handles {
             handle.await.map_err(PreparePtyError::JoinError)?;
         }
+        self.recorder = None;
         Ok(exit_status)
     }
 
+    /// Saves the finished grading and sends its result to the waiting CLI
+    async fn finish_grading(&mut self, grading: Grading) {
+        let response = match grading.handle.await {
+            Ok(graded) => match graded.and_then(|graded| self.save_attempt(graded)) {
+                Ok(tests) => Response::Result { tests },
+                Err(e) => Response::Error {
+                    message: e.to_string(),
+                },
+            },
+            Err(e) => Response::Error {
+                message: e.to_string(),
+            },
+        };
+        // Если обновить статусы не удалось, меню повторит это при следующей отрисовке
+        let _ = self.update_context();
+
+        let _ = grading.reply.send(response);
+    }
+
+    /// Resizes the local screen and the recording at once, the container is resized in the background
+    fn resize_pty(
+        &self,
+        screen: &mut Screen,
+        size: Size,
+        container_name: &str,
+    ) -> tokio::task::JoinHandle<()> {
+        screen.set_size(size.rows, size.cols);
+        if let Some(recorder) = &self.recorder {
+            let _ = recorder
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .resize(size.cols, size.rows);
+        }
+
+        let name = container_name.to_string();
+        tokio::spawn(async move {
+            let _ = resize_container(name, size.rows as i32, size.cols as i32).await;
+        })
+    }
+
     async fn run_pty_bollard(
         &mut self,
         terminal: &mut DefaultTerminal,
         parser: Arc<RwLock<vt100::Parser>>,
         sender: tokio::sync::mpsc::Sender<Bytes>,
         exit_rx: std::sync::mpsc::Receiver<()>,
+        listener: &mut EventListener,
         container_name: String,
     ) -> Result<PtyExitStatus, RunPtyError> {
         let mut handles = Vec::new();
-        let mut status_time = tokio::time::interval(Duration::from_millis(500));
+        let mut input_modes = HostInputModes::enable()?;
+        let repository = self
+            .manifest_of(self.task_under_cursor())
+            .map(|manifest| manifest.repository_path())
+            .unwrap_or_else(|| manifest::STUDENT_HOME.to_string());
+        let spawn_poller = || RepositoryPoller::spawn(container_name.clone(), repository.clone());
+        let mut poller = self.side_panels.git_graph.then(spawn_poller);
+        let mut grading: Option<Grading> = None;
+        let prefix_key = config().ui.prefix_key;
         loop {
-            if status_time.tick().now_or_never().is_some() {
-                let task = self.task_under_cursor();
-                let status_output = docker::exec_command(task, "cat /etc/git-trainer/status").await;
-                if let Ok(cmd) = status_output {
-                    let a = cmd.output.trim();
-                    if a == "1" {
+            if let Some(finished) = grading.take_if(|grading| grading.handle.is_finished()) {
+                self.finish_grading(finished).await;
+            }
+            if let Ok(event) = listener.events.try_recv() {
+                match event.request {
+                    Request::Restart => {
+                        // Результат уже идущей проверки не должен потеряться
+                        if let Some(grading) = grading.take() {
+                            self.finish_grading(grading).await;
+                        }
                         let exit_command = Bytes::from("exit\n");
                         _ = sender.send(exit_command).await;
 
@@ -208,17 +518,44 @@ impl App {
                             }
                         }
                         return Ok(PtyExitStatus::RestartTask);
-                    } else if a == "2" {
-                        let task = self.task_under_cursor();
-                        // самый костыльный костыль. Миша, если ты это читаешь, пойми и прости меня.
-                        let _ = docker::exec_command(task, "git-trainer task").await;
-
-                        self.test_submitted_task().await;
-                        self.update_context();
+                    }
+                    Request::Submit if grading.is_some() => {
+                        let _ = event.reply.send(Response::Error {
+                            message: tr("events.grading_in_progress").to_string(),
+                        });
+                    }
+                    Request::Submit => match self.start_grading() {
+                        Ok(handle) => {
+                            grading = Some(Grading {
+                                handle,
+                                reply: event.reply,
+                            });
+                        }
+                        Err(e) => {
+                            let _ = event.reply.send(Response::Error {
+                                message: e.to_string(),
+                            });
+                        }
+                    },
+                    Request::Hint => {
+                        let response = match self.unlock_next_hint() {
+                            Ok((hints, total, revealed)) => Response::Hints {
+                                hints,
+                                total,
+                                revealed,
+                            },
+                            Err(e) => Response::Error {
+                                message: e.to_string(),
+                            },
+                        };
+                        let _ = event.reply.send(response);
                     }
                 }
             }
             if exit_rx.try_recv().is_ok() {
+                if let Some(grading) = grading.take() {
+                    self.finish_grading(grading).await;
+                }
                 for handle in handles.drain(..) {
                     if let Err(e) = handle.await {
                         return Err(RunPtyError::JoinError(e));
@@ -227,29 +564,101 @@ impl App {
                 return Ok(PtyExitStatus::Exit);
             }
 
-            terminal.draw(|f| self.render_pty(f, parser.read().unwrap().screen()))?;
+            terminal.draw(|f| {
+                let repository = poller
+                    .as_ref()
+                    .map(|poller| poller.view.read().unwrap_or_else(PoisonError::into_inner));
+                self.render_pty(
+                    f,
+                    parser
+                        .read()
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .screen(),
+                    repository.as_deref(),
+                    grading.is_some(),
+                )
+            })?;
+            input_modes.sync_mouse_capture(
+                parser
+                    .read()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .screen(),
+            )?;
 
-            if event::poll(Duration::from_millis(10))? {
+            if event::poll(Duration::from_millis(config().intervals.input_poll_ms))? {
                 match event::read()? {
-                    Event::Key(key) => {
-                        if key.kind == KeyEventKind::Press {
-                            if self.active_popup.is_some() {
-                                let _ = self.handle_popup_key(key.code);
-                            } else {
-                                let _ = self.handle_terminal_key(key.code, key.modifiers, &sender);
+                    Event::Key(key) if key.kind == KeyEventKind::Press => {
+                        let mut parser = parser.write().unwrap_or_else(PoisonError::into_inner);
+                        let screen = parser.screen_mut();
+                        let page = screen.size().0 as usize;
+
+                        if self.active_popup.is_some() {
+                            let _ = self.handle_popup_key(key.code);
+                        } else if self.copy_mode.is_some() {
+                            let _ = self.handle_copy_mode_key(key.code, screen);
+                        } else if self.prefix_pressed {
+                            self.prefix_pressed = false;
+                            match key.code {
+                                KeyCode::Char('t') => {
+                                    self.side_panels.task_description =
+                                        !self.side_panels.task_description;
+                                    let size =
+                                        pty_size(terminal.get_frame().area(), self.side_panels);
+                                    handles.push(self.resize_pty(screen, size, &container_name));
+                                }
+                                KeyCode::Char('g') => {
+                                    self.side_panels.git_graph = !self.side_panels.git_graph;
+                                    poller = self.side_panels.git_graph.then(spawn_poller);
+                                    let size =
+                                        pty_size(terminal.get_frame().area(), self.side_panels);
+                                    handles.push(self.resize_pty(screen, size, &container_name));
+                                }
+                                KeyCode::Char('[') => self.copy_mode = Some(CopyMode::new(screen)),
+                                // Префикс дважды — передать его программе, как в tmux
+                                _ if prefix_key.matches(key.code, key.modifiers) => {
+                                    screen.set_scrollback(0);
+                                    let _ = self.handle_terminal_key(
+                                        key.code,
+                                        key.modifiers,
+                                        screen,
+                                        &sender,
+                                    );
+                                }
+                                _ => {}
                             }
+                        } else if prefix_key.matches(key.code, key.modifiers) {
+                            self.prefix_pressed = true;
+                        } else if key.modifiers.contains(KeyModifiers::SHIFT)
+                            && key.code == KeyCode::PageUp
+                        {
+                            screen.set_scrollback(screen.scrollback() + page);
+                        } else if key.modifiers.contains(KeyModifiers::SHIFT)
+                            && key.code == KeyCode::PageDown
+                        {
+                            screen.set_scrollback(screen.scrollback().saturating_sub(page));
+                        } else {
+                            screen.set_scrollback(0);
+                            let _ =
+                                self.handle_terminal_key(key.code, key.modifiers, screen, &sender);
+                        }
+                    }
+                    Event::Paste(text) if self.active_popup.is_none() => {
+                        let mut parser = parser.write().unwrap_or_else(PoisonError::into_inner);
+                        parser.screen_mut().set_scrollback(0);
+                        let bytes = input::encode_paste(&text, parser.screen().bracketed_paste());
+                        let _ = sender.try_send(Bytes::from(bytes));
+                    }
+                    Event::Mouse(mouse) if self.active_popup.is_none() => {
+                        let area = terminal_area(terminal.get_frame().area(), self.side_panels);
+                        let screen = parser.read().unwrap_or_else(PoisonError::into_inner);
+                        if let Some(bytes) = input::encode_mouse(&mouse, screen.screen(), area) {
+                            let _ = sender.try_send(Bytes::from(bytes));
                         }
                     }
                     Event::Resize(cols, rows) => {
-                        let rows = rows - 4;
-                        let cols = cols - 2;
-                        parser.write().unwrap().screen_mut().set_size(rows, cols);
-                        let name = container_name.clone();
-
-                        let handle = tokio::spawn(async move {
-                            let _ = resize_container(name, rows as i32, cols as i32).await;
-                        });
-                        handles.push(handle);
+                        let size = pty_size(Rect::new(0, 0, cols, rows), self.side_panels);
+                        let mut parser = parser.write().unwrap_or_else(PoisonError::into_inner);
+                        handles.push(self.resize_pty(parser.screen_mut(), size, &container_name));
                     }
                     _ => {}
                 }
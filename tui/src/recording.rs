@@ -0,0 +1,284 @@
+use crate::config::config;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+/// Sessions are recorded in asciicast v2: a JSON header line followed by
+/// one JSON array `[time, code, data]` per event
+const ASCIICAST_VERSION: u32 = 2;
+
+#[derive(Debug, Error)]
+pub enum RecordingError {
+    #[error("While reading recording: {0}")]
+    IoError(#[from] io::Error),
+
+    #[error("While parsing recording: {0}")]
+    ParseError(#[from] serde_json::Error),
+
+    #[error("Unsupported asciicast version {0}")]
+    UnsupportedVersion(u32),
+}
+
+#[derive(Serialize, Deserialize)]
+struct Header {
+    version: u32,
+    width: u16,
+    height: u16,
+    timestamp: Option<i64>,
+}
+
+pub enum RecordingEvent {
+    Output(String),
+    Resize { cols: u16, rows: u16 },
+}
+
+pub struct Recording {
+    pub cols: u16,
+    pub rows: u16,
+    pub events: Vec<(Duration, RecordingEvent)>,
+}
+
+impl Recording {
+    pub fn duration(&self) -> Duration {
+        self.events
+            .last()
+            .map(|(time, _)| *time)
+            .unwrap_or_default()
+    }
+}
+
+pub fn recordings_dir() -> &'static Path {
+    &config().paths.recordings
+}
+
+pub struct Recorder {
+    path: PathBuf,
+    writer: BufWriter<File>,
+    start: Instant,
+    /// Tail of the output that ends in the middle of a UTF-8 character
+    pending: Vec<u8>,
+}
+
+impl Recorder {
+    pub fn create(name: &str, cols: u16, rows: u16) -> io::Result<Recorder> {
+        fs::create_dir_all(recordings_dir())?;
+        let path = recordings_dir().join(format!(
+            "{}_{}.cast",
+            name,
+            Utc::now().format("%Y%m%d%H%M%S")
+        ));
+
+        let mut writer = BufWriter::new(File::create(&path)?);
+        let header = Header {
+            version: ASCIICAST_VERSION,
+            width: cols,
+            height: rows,
+            timestamp: Some(Utc::now().timestamp()),
+        };
+        writeln!(writer, "{}", serde_json::to_string(&header)?)?;
+
+        Ok(Recorder {
+            path,
+            writer,
+            start: Instant::now(),
+            pending: Vec::new(),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn write_event(&mut self, code: &str, data: &str) -> io::Result<()> {
+        let time = self.start.elapsed().as_secs_f64();
+        writeln!(
+            self.writer,
+            "{}",
+            serde_json::to_string(&(time, code, data))?
+        )
+    }
+
+    pub fn output(&mut self, bytes: &[u8]) -> io::Result<()> {
+        self.pending.extend_from_slice(bytes);
+
+        let valid = match std::str::from_utf8(&self.pending) {
+            Ok(_) => self.pending.len(),
+            // Неполный символ в конце дописывается со следующим куском вывода
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(_) => self.pending.len(),
+        };
+        if valid == 0 {
+            return Ok(());
+        }
+
+        let data = String::from_utf8_lossy(&self.pending[..valid]).to_string();
+        self.pending.drain(..valid);
+        self.write_event("o", &data)
+    }
+
+    /// Output stays in the buffer until a resize or the end of the session
+    pub fn resize(&mut self, cols: u16, rows: u16) -> io::Result<()> {
+        self.write_event("r", &format!("{}x{}", cols, rows))?;
+        self.writer.flush()
+    }
+}
+
+impl Drop for Recorder {
+    fn drop(&mut self) {
+        // Оборванный символ в конце сессии сохраняется как есть
+        if !self.pending.is_empty() {
+            let data = String::from_utf8_lossy(&self.pending).to_string();
+            let _ = self.write_event("o", &data);
+        }
+        let _ = self.writer.flush();
+    }
+}
+
+pub fn load_recording(path: &Path) -> Result<Recording, RecordingError> {
+    let mut lines = BufReader::new(File::open(path)?).lines();
+
+    let header: Header = match lines.next() {
+        Some(line) => serde_json::from_str(&line?)?,
+        None => return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into()),
+    };
+    if header.version != ASCIICAST_VERSION {
+        return Err(RecordingError::UnsupportedVersion(header.version));
+    }
+
+    let mut events = Vec::new();
+    for line in lines {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let (time, code, data): (f64, String, String) = serde_json::from_str(&line)?;
+        let event = match code.as_str() {
+            "o" => RecordingEvent::Output(data),
+            "r" => match data.split_once('x') {
+                Some((cols, rows)) => match (cols.parse(), rows.parse()) {
+                    (Ok(cols), Ok(rows)) => RecordingEvent::Resize { cols, rows },
+                    _ => continue,
+                },
+                None => continue,
+            },
+            // Ввод и маркеры при воспроизведении не нужны
+            _ => continue,
+        };
+        // Отрицательное, бесконечное или слишком большое время — испорченное событие
+        let Ok(time) = Duration::try_from_secs_f64(time) else {
+            continue;
+        };
+        events.push((time, event));
+    }
+
+    Ok(Recording {
+        cols: header.width,
+        rows: header.height,
+        events,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    fn temp_path(name: &str) -> PathBuf {
+        env::temp_dir().join(format!("git-trainer-{}-{}.cast", name, std::process::id()))
+    }
+
+    fn recorder(path: &Path) -> Recorder {
+        let mut writer = BufWriter::new(File::create(path).unwrap());
+        let header = Header {
+            version: ASCIICAST_VERSION,
+            width: 80,
+            height: 24,
+            timestamp: None,
+        };
+        writeln!(writer, "{}", serde_json::to_string(&header).unwrap()).unwrap();
+        Recorder {
+            path: path.to_path_buf(),
+            writer,
+            start: Instant::now(),
+            pending: Vec::new(),
+        }
+    }
+
+    fn outputs(recording: &Recording) -> Vec<&str> {
+        recording
+            .events
+            .iter()
+            .filter_map(|(_, event)| match event {
+                RecordingEvent::Output(data) => Some(data.as_str()),
+                RecordingEvent::Resize { .. } => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn round_trip() {
+        let path = temp_path("round_trip");
+        let mut rec = recorder(&path);
+        rec.output(b"$ git status\r\n").unwrap();
+        rec.resize(100, 30).unwrap();
+        rec.output(b"On branch main\r\n").unwrap();
+        drop(rec);
+
+        let recording = load_recording(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!((recording.cols, recording.rows), (80, 24));
+        assert_eq!(recording.events.len(), 3);
+        assert!(matches!(
+            recording.events[1].1,
+            RecordingEvent::Resize {
+                cols: 100,
+                rows: 30
+            }
+        ));
+        assert_eq!(
+            outputs(&recording),
+            ["$ git status\r\n", "On branch main\r\n"]
+        );
+    }
+
+    #[test]
+    fn utf8_split_across_chunks() {
+        let path = temp_path("utf8_split");
+        let mut rec = recorder(&path);
+        let text = "ветка".as_bytes();
+        rec.output(&text[..3]).unwrap();
+        rec.output(&text[3..]).unwrap();
+        // Оборванный символ в конце записи не теряется
+        rec.output(&"ё".as_bytes()[..1]).unwrap();
+        drop(rec);
+
+        let recording = load_recording(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(outputs(&recording), ["в", "етка", "\u{FFFD}"]);
+    }
+
+    #[test]
+    fn broken_timestamps_are_skipped() {
+        let path = temp_path("broken_timestamps");
+        fs::write(
+            &path,
+            concat!(
+                "{\"version\":2,\"width\":80,\"height\":24}\n",
+                "[1e300,\"o\",\"huge\"]\n",
+                "[-1.0,\"o\",\"negative\"]\n",
+                "[0.5,\"o\",\"ok\"]\n",
+            ),
+        )
+        .unwrap();
+
+        let recording = load_recording(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(outputs(&recording), ["ok"]);
+        assert_eq!(recording.duration(), Duration::from_millis(500));
+    }
+}